@@ -1,13 +1,41 @@
+use std::cell::RefCell;
+
+#[cfg(not(test))]
+pub(crate) use proc_macro::Span;
+#[cfg(test)]
+pub(crate) use proc_macro2::Span;
+
 #[derive(Debug)]
 pub(crate) struct XFlags {
     pub(crate) src: Option<String>,
+    /// Markdown file with a CLI reference to update in place.
+    pub(crate) markdown: Option<String>,
+    /// Traits derived for generated types, unless a command overrides them.
+    pub(crate) derives: Vec<String>,
+    /// Visibility of generated types and fields, `pub` by default.
+    pub(crate) vis: String,
+    pub(crate) subcommand_field: String,
+    pub(crate) enum_suffix: String,
+    /// Name of the program in usage lines, instead of the one from `argv[0]`.
+    pub(crate) program: Option<String>,
+    /// Whether to generate `to_args`, which needs `Display` for `FromStr` types.
+    pub(crate) to_args: bool,
     pub(crate) cmd: Cmd,
+    /// DSL tokens referenced from the generated code, filled by `emit`. They
+    /// are emitted as placeholders which are then replaced with the original
+    /// tokens, so that type errors and go-to-definition point at the DSL.
+    pub(crate) spans: RefCell<Vec<(String, Span)>>,
 }
 
 impl XFlags {
     pub fn is_anon(&self) -> bool {
         self.cmd.name.is_empty()
     }
+
+    /// Name of the top-level command in usage lines.
+    pub(crate) fn program_name(&self) -> &str {
+        self.program.as_deref().unwrap_or(&self.cmd.name)
+    }
 }
 
 #[derive(Debug)]
@@ -19,7 +47,25 @@ pub(crate) struct Cmd {
     pub(crate) flags: Vec<Flag>,
     pub(crate) subcommands: Vec<Cmd>,
     pub(crate) default: bool,
+    pub(crate) examples: Vec<Example>,
+    /// Free-form text at the end of help.
+    pub(crate) after_help: Option<String>,
+    /// Heading to list the command under in the parent's help.
+    pub(crate) section: Option<String>,
+    pub(crate) derives: Option<Vec<String>>,
+    pub(crate) attrs: Vec<String>,
+    /// Doc comment and attributes of the subcommand enum, from an `enum` item.
+    pub(crate) enum_doc: Option<String>,
+    pub(crate) enum_attrs: Vec<String>,
     pub(crate) idx: u8,
+    /// Where the command is named in the DSL.
+    pub(crate) span: Span,
+}
+
+#[derive(Debug)]
+pub(crate) struct Example {
+    pub(crate) cmdline: String,
+    pub(crate) doc: Option<String>,
 }
 
 #[derive(Debug)]
@@ -27,6 +73,11 @@ pub(crate) struct Arg {
     pub(crate) arity: Arity,
     pub(crate) doc: Option<String>,
     pub(crate) val: Val,
+    /// Name of the generated field, if it differs from the argument name.
+    pub(crate) field: Option<String>,
+    pub(crate) attrs: Vec<String>,
+    /// Where the field name comes from in the DSL.
+    pub(crate) span: Span,
 }
 
 #[derive(Debug)]
@@ -36,6 +87,14 @@ pub(crate) struct Flag {
     pub(crate) short: Option<String>,
     pub(crate) doc: Option<String>,
     pub(crate) val: Option<Val>,
+    pub(crate) delimiter: Option<char>,
+    /// Name of the generated field, if it differs from the flag name.
+    pub(crate) field: Option<String>,
+    /// Heading to list the flag under in help.
+    pub(crate) section: Option<String>,
+    pub(crate) attrs: Vec<String>,
+    /// Where the field name comes from in the DSL.
+    pub(crate) span: Span,
 }
 
 impl Flag {
@@ -48,13 +107,19 @@ impl Flag {
 pub(crate) enum Arity {
     Optional,
     Required,
-    Repeated,
+    /// Occurs between `min` and `max` (inclusive) times, unbounded if `max` is `None`.
+    Repeated {
+        min: usize,
+        max: Option<usize>,
+    },
 }
 
 #[derive(Debug)]
 pub(crate) struct Val {
     pub(crate) name: String,
     pub(crate) ty: Ty,
+    /// Where the type is named in the DSL.
+    pub(crate) span: Span,
 }
 
 #[derive(Debug)]
@@ -1,4 +1,7 @@
-use crate::{ast, update};
+use crate::{
+    ast::{self, Span},
+    json, markdown, update,
+};
 
 use std::{env, fmt::Write, path::Path};
 
@@ -13,15 +16,19 @@ pub(crate) fn emit(xflags: &ast::XFlags) -> String {
         w!(buf, "{{\n");
     }
 
-    emit_cmd(&mut buf, &xflags.cmd);
+    emit_cmd(&mut buf, xflags, &xflags.cmd);
     blank_line(&mut buf);
     emit_api(&mut buf, xflags);
 
     if !xflags.is_anon() && env::var("UPDATE_XFLAGS").is_ok() {
+        let text = unspanned(xflags, &buf);
         if let Some(src) = &xflags.src {
-            update::in_place(&buf, Path::new(src.as_str()))
+            update::in_place(&text, Path::new(src.as_str()))
         } else {
-            update::stdout(&buf);
+            update::stdout(&text);
+        }
+        if let Some(path) = &xflags.markdown {
+            update::markdown(&markdown::render(xflags), Path::new(path.as_str()))
         }
     }
 
@@ -41,9 +48,11 @@ pub(crate) fn emit(xflags: &ast::XFlags) -> String {
     buf
 }
 
-fn emit_cmd(buf: &mut String, cmd: &ast::Cmd) {
-    w!(buf, "#[derive(Debug)]\n");
-    w!(buf, "pub struct {}", cmd.ident());
+fn emit_cmd(buf: &mut String, xflags: &ast::XFlags, cmd: &ast::Cmd) {
+    let derives = cmd.derives.as_ref().unwrap_or(&xflags.derives);
+    emit_doc(buf, "", cmd.doc.as_deref());
+    emit_attrs(buf, derives, &cmd.attrs);
+    w!(buf, "{} struct {}", xflags.vis, spanned(xflags, &cmd.ident(), cmd.span));
     let flags = cmd.flags.iter().filter(|it| !it.is_help()).collect::<Vec<_>>();
     if cmd.args.is_empty() && flags.is_empty() && cmd.subcommands.is_empty() {
         w!(buf, ";\n");
@@ -52,8 +61,11 @@ fn emit_cmd(buf: &mut String, cmd: &ast::Cmd) {
     w!(buf, " {{\n");
 
     for arg in &cmd.args {
-        let ty = gen_arg_ty(arg.arity, &arg.val.ty);
-        w!(buf, "    pub {}: {ty},\n", arg.val.ident());
+        let ty = gen_arg_ty(xflags, arg.arity, &arg.val);
+        let field = spanned(xflags, &arg.field(), arg.span);
+        emit_doc(buf, "    ", arg.doc.as_deref());
+        emit_attrs(buf, &[], &arg.attrs);
+        w!(buf, "    {} {field}: {ty},\n", xflags.vis);
     }
 
     if !cmd.args.is_empty() && !flags.is_empty() {
@@ -61,53 +73,95 @@ fn emit_cmd(buf: &mut String, cmd: &ast::Cmd) {
     }
 
     for flag in &flags {
-        let ty = gen_flag_ty(flag.arity, flag.val.as_ref().map(|it| &it.ty));
-        w!(buf, "    pub {}: {ty},\n", flag.ident());
+        let ty = gen_flag_ty(xflags, flag);
+        let field = spanned(xflags, &flag.field(), flag.span);
+        emit_doc(buf, "    ", flag.doc.as_deref());
+        emit_attrs(buf, &[], &flag.attrs);
+        w!(buf, "    {} {field}: {ty},\n", xflags.vis);
     }
 
     if cmd.has_subcommands() {
-        w!(buf, "    pub subcommand: {},\n", cmd.cmd_enum_ident());
+        w!(
+            buf,
+            "    {} {}: {},\n",
+            xflags.vis,
+            raw(&xflags.subcommand_field),
+            cmd.cmd_enum_ident(xflags)
+        );
     }
     w!(buf, "}}\n");
 
     if cmd.has_subcommands() {
+        // `#[derive(Default)]` doesn't work for enums with data, implement it by hand.
+        let (default, derives): (Vec<_>, Vec<_>) =
+            derives.iter().cloned().partition(|it| it == "Default");
         blank_line(buf);
-        w!(buf, "#[derive(Debug)]\n");
-        w!(buf, "pub enum {} {{\n", cmd.cmd_enum_ident());
+        emit_doc(buf, "", cmd.enum_doc.as_deref());
+        emit_attrs(buf, &derives, &cmd.enum_attrs);
+        w!(buf, "{} enum {} {{\n", xflags.vis, cmd.cmd_enum_ident(xflags));
         for sub in &cmd.subcommands {
             let name = sub.ident();
-            w!(buf, "    {name}({name}),\n");
+            let variant = spanned(xflags, &name, sub.span);
+            emit_doc(buf, "    ", sub.doc.as_deref());
+            w!(buf, "    {variant}({name}),\n");
         }
         w!(buf, "}}\n");
 
+        if !default.is_empty() {
+            // The default subcommand, if any, comes first.
+            let name = cmd.subcommands[0].ident();
+            blank_line(buf);
+            w!(buf, "impl Default for {} {{\n", cmd.cmd_enum_ident(xflags));
+            w!(buf, "    fn default() -> Self {{\n");
+            w!(buf, "        Self::{name}({name}::default())\n");
+            w!(buf, "    }}\n");
+            w!(buf, "}}\n");
+        }
+
         for sub in &cmd.subcommands {
             blank_line(buf);
-            emit_cmd(buf, sub);
+            emit_cmd(buf, xflags, sub);
+        }
+    }
+}
+
+fn emit_doc(buf: &mut String, indent: &str, doc: Option<&str>) {
+    for line in doc.into_iter().flat_map(str::lines) {
+        if line.is_empty() {
+            w!(buf, "{indent}///\n");
+        } else {
+            w!(buf, "{indent}/// {line}\n");
         }
     }
 }
 
-fn gen_flag_ty(arity: ast::Arity, ty: Option<&ast::Ty>) -> String {
-    match ty {
-        None => match arity {
+fn emit_attrs(buf: &mut String, derives: &[String], attrs: &[String]) {
+    if !derives.is_empty() {
+        w!(buf, "#[derive({})]\n", derives.join(", "));
+    }
+    for attr in attrs {
+        w!(buf, "{attr}\n");
+    }
+}
+
+fn gen_flag_ty(xflags: &ast::XFlags, flag: &ast::Flag) -> String {
+    match &flag.val {
+        None => match flag.arity {
             ast::Arity::Optional => "bool".to_string(),
             ast::Arity::Required => "()".to_string(),
-            ast::Arity::Repeated => "u32".to_string(),
+            ast::Arity::Repeated { .. } => "u32".to_string(),
         },
-        Some(ty) => gen_arg_ty(arity, ty),
+        Some(val) if flag.delimiter.is_some() => format!("Vec<{}>", val.spanned(xflags)),
+        Some(val) => gen_arg_ty(xflags, flag.arity, val),
     }
 }
 
-fn gen_arg_ty(arity: ast::Arity, ty: &ast::Ty) -> String {
-    let ty = match ty {
-        ast::Ty::PathBuf => "PathBuf".into(),
-        ast::Ty::OsString => "OsString".into(),
-        ast::Ty::FromStr(it) => it.clone(),
-    };
+fn gen_arg_ty(xflags: &ast::XFlags, arity: ast::Arity, val: &ast::Val) -> String {
+    let ty = val.spanned(xflags);
     match arity {
         ast::Arity::Optional => format!("Option<{}>", ty),
         ast::Arity::Required => ty,
-        ast::Arity::Repeated => format!("Vec<{}>", ty),
+        ast::Arity::Repeated { .. } => format!("Vec<{}>", ty),
     }
 }
 
@@ -130,6 +184,54 @@ fn emit_api(buf: &mut String, xflags: &ast::XFlags) {
     w!(buf, "    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {{\n");
     w!(buf, "        Self::from_vec_(args)\n");
     w!(buf, "    }}\n");
+    blank_line(buf);
+
+    w!(buf, "    #[allow(dead_code)]\n");
+    w!(buf, "    pub fn from_args<I>(args: I) -> xflags::Result<Self>\n");
+    w!(buf, "    where\n");
+    w!(buf, "        I: IntoIterator,\n");
+    w!(buf, "        I::Item: Into<std::ffi::OsString>,\n");
+    w!(buf, "    {{\n");
+    w!(buf, "        Self::from_vec_(args.into_iter().map(Into::into).collect())\n");
+    w!(buf, "    }}\n");
+    blank_line(buf);
+
+    w!(buf, "    #[allow(dead_code)]\n");
+    w!(buf, "    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {{\n");
+    w!(buf, "        Self::from_args(args.iter().copied())\n");
+    w!(buf, "    }}\n");
+    blank_line(buf);
+
+    w!(buf, "    #[allow(dead_code)]\n");
+    w!(buf, "    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>\n");
+    w!(buf, "    where\n");
+    w!(buf, "        I: IntoIterator,\n");
+    w!(buf, "        I::Item: Into<std::ffi::OsString>,\n");
+    w!(buf, "    {{\n");
+    w!(buf, "        Self::from_argv_(argv.into_iter().map(Into::into).collect())\n");
+    w!(buf, "    }}\n");
+    blank_line(buf);
+
+    w!(buf, "    #[allow(dead_code)]\n");
+    w!(buf, "    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {{\n");
+    w!(buf, "        Self::from_command_line_(command_line)\n");
+    w!(buf, "    }}\n");
+    blank_line(buf);
+
+    w!(buf, "    #[allow(dead_code)]\n");
+    w!(buf, "    pub fn from_vec_partial(\n");
+    w!(buf, "        args: Vec<std::ffi::OsString>,\n");
+    w!(buf, "    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {{\n");
+    w!(buf, "        Self::from_vec_partial_(args)\n");
+    w!(buf, "    }}\n");
+
+    if xflags.to_args {
+        blank_line(buf);
+        w!(buf, "    #[allow(dead_code)]\n");
+        w!(buf, "    pub fn to_args(&self) -> Vec<std::ffi::OsString> {{\n");
+        w!(buf, "        self.to_args_()\n");
+        w!(buf, "    }}\n");
+    }
     w!(buf, "}}\n");
 }
 
@@ -146,47 +248,93 @@ fn emit_impls(buf: &mut String, xflags: &ast::XFlags) {
     w!(buf, "        let mut p = xflags::rt::Parser::new(args);\n");
     w!(buf, "        Self::parse_(&mut p)\n");
     w!(buf, "    }}\n");
+    w!(buf, "    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {{\n");
+    w!(buf, "        let mut p = xflags::rt::Parser::new_from_argv(argv);\n");
+    w!(buf, "        Self::parse_(&mut p)\n");
+    w!(buf, "    }}\n");
+    w!(buf, "    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {{\n");
+    w!(buf, "        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;\n");
+    w!(buf, "        Self::parse_(&mut p)\n");
+    w!(buf, "    }}\n");
+    w!(
+        buf,
+        "    fn from_vec_partial_(args: Vec<std::ffi::OsString>) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {{\n"
+    );
+    w!(buf, "        let mut p = xflags::rt::Parser::new_partial(args);\n");
+    w!(buf, "        let res = Self::parse_(&mut p)?;\n");
+    w!(buf, "        Ok((res, p.into_rest()))\n");
+    w!(buf, "    }}\n");
+    if xflags.to_args {
+        w!(buf, "    fn to_args_(&self) -> Vec<std::ffi::OsString> {{\n");
+        w!(buf, "        let mut w = xflags::rt::Unparser::default();\n");
+        w!(buf, "        self.unparse_(&mut w);\n");
+        w!(buf, "        w.finish()\n");
+        w!(buf, "    }}\n");
+    }
     w!(buf, "}}\n");
     blank_line(buf);
-    emit_parse(buf, &xflags.cmd)
+    emit_parse(buf, xflags);
+    if xflags.to_args {
+        emit_unparse_rec(buf, xflags, &xflags.cmd);
+    }
 }
 
-fn emit_parse(buf: &mut String, cmd: &ast::Cmd) {
+fn emit_parse(buf: &mut String, xflags: &ast::XFlags) {
+    let cmd = &xflags.cmd;
     w!(buf, "impl {} {{\n", cmd.ident());
     w!(buf, "fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {{\n");
     w!(buf, "#![allow(non_snake_case, unused_mut)]\n");
+    if xflags.program.is_none() {
+        w!(buf, "p_.set_command_name({});\n", str_lit(&cmd.name));
+    }
 
     let mut prefix = String::new();
     emit_locals_rec(buf, &mut prefix, cmd);
     blank_line(buf);
     w!(buf, "let mut state_ = 0u8;\n");
+    w!(buf, "let mut help_ = false;\n");
+    w!(buf, "let mut parse_ = || -> xflags::Result<Self> {{\n");
 
-    // No while loop needed for command with no items (clippy::never_loop)
-    if cmd.args.len() + cmd.flags.len() + cmd.subcommands.len() <= 1 {
-        w!(buf, "if let Some(arg_) = p_.pop_flag() {{\n");
-    } else {
-        w!(buf, "while let Some(arg_) = p_.pop_flag() {{\n");
-    }
+    w!(buf, "while let Some(arg_) = p_.pop_flag() {{\n");
 
     w!(buf, "match arg_ {{\n");
     {
         w!(buf, "Ok(flag_) => match (state_, flag_.as_str()) {{\n");
-        emit_match_flag_rec(buf, &mut prefix, cmd);
-        w!(buf, "_ => return Err(p_.unexpected_flag(&flag_)),\n");
+        emit_match_flag_rec(buf, xflags, &mut prefix, cmd);
+        w!(buf, "_ => p_.unexpected_flag(flag_)?,\n");
         w!(buf, "}}\n");
 
         w!(buf, "Err(arg_) => match (state_, arg_.to_str().unwrap_or(\"\")) {{\n");
-        emit_match_arg_rec(buf, &mut prefix, cmd);
-        w!(buf, "_ => return Err(p_.unexpected_arg(arg_)),\n");
+        emit_match_arg_rec(buf, xflags, &mut prefix, cmd, &[]);
+        w!(buf, "_ => p_.unexpected_arg(arg_)?,\n");
         w!(buf, "}}\n");
     }
     w!(buf, "}}\n");
     w!(buf, "}}\n");
+    w!(buf, "if help_ {{\n");
+    w!(buf, "let help_ = match state_ {{\n");
+    emit_help_by_state_rec(buf, &mut prefix, cmd);
+    w!(buf, "_ => unreachable!(),\n");
+    w!(buf, "}};\n");
+    w!(buf, "return Err(p_.help(help_));\n");
+    w!(buf, "}}\n");
+    emit_trailing_args_rec(buf, xflags, &mut prefix, cmd);
     emit_default_transitions(buf, cmd);
+    emit_check_rec(buf, &mut prefix, cmd);
+    w!(buf, "p_.finish_checks()?;\n");
 
     w!(buf, "Ok(");
-    emit_record_rec(buf, &mut prefix, cmd);
+    emit_record_rec(buf, xflags, &mut prefix, cmd);
     w!(buf, ")");
+    w!(buf, "}};\n");
+
+    w!(buf, "parse_().map_err(|err_| {{\n");
+    w!(buf, "let (command_, usage_, help_command_) = match state_ {{\n");
+    emit_usage_by_state_rec(buf, &mut vec![xflags.program_name()], cmd);
+    w!(buf, "_ => unreachable!(),\n");
+    w!(buf, "}};\n");
+    w!(buf, "p_.with_usage(err_, command_, usage_, help_command_)\n");
+    w!(buf, "}})\n");
 
     w!(buf, "}}\n");
     w!(buf, "}}\n");
@@ -195,11 +343,14 @@ fn emit_parse(buf: &mut String, cmd: &ast::Cmd) {
 fn emit_locals_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
     for flag in &cmd.flags {
         if !flag.is_help() {
-            w!(buf, "let mut {prefix}{} = Vec::new();\n", flag.ident());
+            w!(buf, "let mut {} = Vec::new();\n", flag.local(prefix));
         }
     }
     for arg in &cmd.args {
-        w!(buf, "let mut {prefix}{} = (false, Vec::new());\n", arg.val.ident());
+        w!(buf, "let mut {} = (false, Vec::new());\n", arg.local(prefix));
+    }
+    if !cmd.trailing_args().is_empty() {
+        w!(buf, "let mut {prefix}rest_ = Vec::new();\n");
     }
     for sub in &cmd.subcommands {
         let l = sub.push_prefix(prefix);
@@ -208,29 +359,38 @@ fn emit_locals_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
     }
 }
 
-fn emit_match_flag_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
-    w!(
-        buf,
-        "({}, \"--help\" | \"-h\") => return Err(p_.help(Self::HELP_{})),\n",
-        cmd.idx,
-        snake(prefix).to_uppercase()
-    );
+fn emit_match_flag_rec(
+    buf: &mut String,
+    xflags: &ast::XFlags,
+    prefix: &mut String,
+    cmd: &ast::Cmd,
+) {
+    let name = snake(prefix).to_uppercase();
+    w!(buf, "({}, \"--help\") => return Err(p_.help(Self::HELP_{name})),\n", cmd.idx);
+    w!(buf, "({}, \"-h\") => return Err(p_.help(Self::HELP_SHORT_{name})),\n", cmd.idx);
     for flag in cmd.flags.iter().filter(|f| !f.is_help()) {
         w!(buf, "(");
-        emit_all_ids_rec(buf, cmd);
+        emit_all_ids(buf, cmd);
         w!(buf, ", \"--{}\"", flag.name);
         if let Some(short) = &flag.short {
             w!(buf, "| \"-{short}\"");
         }
         w!(buf, ") => ");
-        w!(buf, "{prefix}{}.push(", flag.ident());
+        w!(buf, "{}.push(", flag.local(prefix));
         match &flag.val {
-            Some(val) => match &val.ty {
-                ast::Ty::OsString | ast::Ty::PathBuf => {
-                    w!(buf, "p_.next_value(&flag_)?.into()")
+            Some(val) => match (&val.ty, flag.delimiter) {
+                (ast::Ty::OsString, None) => w!(buf, "p_.next_value(&flag_)?"),
+                (ast::Ty::PathBuf, None) => w!(buf, "p_.next_value(&flag_)?.into()"),
+                (ast::Ty::FromStr(_), None) => {
+                    let (m, ty) = (val.method(xflags, "next_value_from_str"), val.spanned(xflags));
+                    w!(buf, "p_.{m}::<{ty}>(&flag_, \"{}\")?", val.ty.name())
                 }
-                ast::Ty::FromStr(ty) => {
-                    w!(buf, "p_.next_value_from_str::<{ty}>(&flag_)?")
+                (ast::Ty::OsString | ast::Ty::PathBuf, Some(d)) => {
+                    w!(buf, "p_.next_values::<{}>(&flag_, {d:?})?", val.spanned(xflags))
+                }
+                (ast::Ty::FromStr(_), Some(d)) => {
+                    let (m, ty) = (val.method(xflags, "next_values_from_str"), val.spanned(xflags));
+                    w!(buf, "p_.{m}::<{ty}>(&flag_, \"{}\", {d:?})?", val.ty.name())
                 }
             },
             None => w!(buf, "()"),
@@ -242,135 +402,365 @@ fn emit_match_flag_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
     }
     for sub in &cmd.subcommands {
         let l = sub.push_prefix(prefix);
-        emit_match_flag_rec(buf, prefix, sub);
+        emit_match_flag_rec(buf, xflags, prefix, sub);
         prefix.truncate(l);
     }
 }
 
-fn emit_match_arg_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
+/// `parents` are the commands whose positionals continue into `cmd`, as it
+/// is their default subcommand, with their prefixes.
+fn emit_match_arg_rec(
+    buf: &mut String,
+    xflags: &ast::XFlags,
+    prefix: &mut String,
+    cmd: &ast::Cmd,
+    parents: &[(String, &ast::Cmd)],
+) {
     for sub in cmd.named_subcommands() {
         let sub_match =
             sub.all_identifiers().map(|s| format!("\"{s}\"")).collect::<Vec<_>>().join(" | ");
-        w!(buf, "({}, {}) => state_ = {},\n", cmd.idx, sub_match, sub.idx);
+        w!(buf, "({}, {}) => {{\n", cmd.idx, sub_match);
+        w!(buf, "state_ = {};\n", sub.idx);
+        w!(buf, "p_.enter_subcommand();\n");
+        w!(buf, "}}\n");
     }
 
-    if cmd.args.is_empty() {
-        // add `help` subcommand only if command takes no args to make sure it doesn't take precedence
-        w!(
-            buf,
-            "({}, \"help\") => return Err(p_.help(Self::HELP_{})),\n",
-            cmd.idx,
-            snake(prefix).to_uppercase()
-        );
+    if cmd.has_help_command() {
+        // The help is shown after the loop, so that `help <commands>` selects the subcommand.
+        w!(buf, "({}, \"help\") if !help_ && !p_.after_double_dash() => help_ = true,\n", cmd.idx);
     }
 
-    if !cmd.args.is_empty() || cmd.has_subcommands() {
+    let has_parent_args = parents.iter().any(|(_, it)| !it.args.is_empty());
+    if !cmd.args.is_empty() || cmd.has_subcommands() || has_parent_args {
         w!(buf, "({}, _) => {{\n", cmd.idx);
-        for arg in &cmd.args {
-            let done = match arg.arity {
-                ast::Arity::Optional | ast::Arity::Required => "done_ @ ",
-                ast::Arity::Repeated => "",
-            };
-            w!(buf, "if let ({done}false, buf_) = &mut {prefix}{} {{\n", arg.val.ident());
-            w!(buf, "buf_.push(");
-            match &arg.val.ty {
-                ast::Ty::OsString | ast::Ty::PathBuf => {
-                    w!(buf, "arg_.into()")
-                }
-                ast::Ty::FromStr(ty) => {
-                    w!(buf, "p_.value_from_str::<{ty}>(\"{}\", arg_)?", arg.val.name);
-                }
-            }
-            w!(buf, ");\n");
-            match arg.arity {
-                ast::Arity::Optional | ast::Arity::Required => {
-                    w!(buf, "*done_ = true;\n");
-                }
-                ast::Arity::Repeated => (),
-            }
-            w!(buf, "continue;\n");
-            w!(buf, "}}\n");
+        // Switches of a default subcommand may come before the positionals of
+        // its parent, which are filled first.
+        let mut takes_all = false;
+        for (parent_prefix, parent) in parents {
+            takes_all = takes_all || emit_push_arg(buf, xflags, parent_prefix, parent);
         }
+        takes_all = takes_all || emit_push_arg(buf, xflags, prefix, cmd);
 
-        if let Some(sub) = cmd.default_subcommand() {
-            w!(buf, "p_.push_back(Err(arg_)); state_ = {};", sub.idx);
-        } else {
-            w!(buf, "return Err(p_.unexpected_arg(arg_));");
+        match cmd.default_subcommand() {
+            _ if takes_all => (),
+            Some(sub) => w!(buf, "p_.push_back(Err(arg_)); state_ = {};", sub.idx),
+            None => w!(buf, "p_.unexpected_arg(arg_)?;"),
         }
 
         w!(buf, "}}\n");
     }
 
+    for (i, sub) in cmd.subcommands.iter().enumerate() {
+        let mut sub_parents = Vec::new();
+        if cmd.default && i == 0 {
+            sub_parents.extend(parents.iter().cloned());
+            sub_parents.push((prefix.clone(), cmd));
+        }
+        let l = sub.push_prefix(prefix);
+        emit_match_arg_rec(buf, xflags, prefix, sub, &sub_parents);
+        prefix.truncate(l);
+    }
+}
+
+/// Pushes `arg_` to the first positional of `cmd` that takes it, returns
+/// whether one always does.
+fn emit_push_arg(buf: &mut String, xflags: &ast::XFlags, prefix: &str, cmd: &ast::Cmd) -> bool {
+    let trailing = cmd.trailing_args().len();
+    for arg in &cmd.args[..cmd.args.len() - trailing] {
+        if trailing > 0 && matches!(arg.arity, ast::Arity::Repeated { .. }) {
+            // Distributed once all arguments are known, see `emit_trailing_args_rec`.
+            w!(buf, "{prefix}rest_.push((p_.position(), arg_));\n");
+            return true;
+        }
+        let done = match arg.arity {
+            ast::Arity::Optional | ast::Arity::Required => "done_ @ ",
+            ast::Arity::Repeated { .. } => "",
+        };
+        w!(buf, "if let ({done}false, buf_) = &mut {} {{\n", arg.local(prefix));
+        w!(buf, "buf_.push({});\n", arg_value(xflags, arg));
+        match arg.arity {
+            ast::Arity::Optional | ast::Arity::Required => {
+                w!(buf, "*done_ = true;\n");
+            }
+            ast::Arity::Repeated { .. } => (),
+        }
+        w!(buf, "continue;\n");
+        w!(buf, "}}\n");
+    }
+    false
+}
+
+fn emit_help_by_state_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
+    w!(buf, "{} => Self::HELP_{},\n", cmd.idx, snake(prefix).to_uppercase());
+    for sub in &cmd.subcommands {
+        let l = sub.push_prefix(prefix);
+        emit_help_by_state_rec(buf, prefix, sub);
+        prefix.truncate(l);
+    }
+}
+
+/// `path` starts with the name of the program.
+fn emit_usage_by_state_rec<'a>(buf: &mut String, path: &mut Vec<&'a str>, cmd: &'a ast::Cmd) {
+    let command = path.join(" ");
+    let usage = usage_line(cmd, &command);
+    let help_command = cmd.has_help_command();
+    w!(buf, "{} => ({}, {}, {help_command}),\n", cmd.idx, str_lit(&command), str_lit(&usage));
+    for sub in &cmd.subcommands {
+        path.push(&sub.name);
+        emit_usage_by_state_rec(buf, path, sub);
+        path.pop();
+    }
+}
+
+/// `cp SRC... DST`: arguments after a repeated one are taken from the end.
+fn emit_trailing_args_rec(
+    buf: &mut String,
+    xflags: &ast::XFlags,
+    prefix: &mut String,
+    cmd: &ast::Cmd,
+) {
+    let trailing = cmd.trailing_args();
+    if !trailing.is_empty() {
+        let rest = format!("{prefix}rest_");
+        let repeated = &cmd.args[cmd.args.len() - trailing.len() - 1];
+        w!(buf, "let tail_ = {rest}.split_off({rest}.len().saturating_sub({}));\n", trailing.len());
+        w!(buf, "for (position_, arg_) in {rest} {{\n");
+        w!(buf, "p_.set_position(position_);\n");
+        w!(buf, "{}.1.push({});\n", repeated.local(prefix), arg_value(xflags, repeated));
+        w!(buf, "}}\n");
+        w!(buf, "let mut tail_ = tail_.into_iter();\n");
+        for arg in trailing {
+            w!(buf, "if let Some((position_, arg_)) = tail_.next() {{\n");
+            w!(buf, "p_.set_position(position_);\n");
+            w!(buf, "{}.1.push({});\n", arg.local(prefix), arg_value(xflags, arg));
+            w!(buf, "}}\n");
+        }
+    }
+    for sub in &cmd.subcommands {
+        let l = sub.push_prefix(prefix);
+        emit_trailing_args_rec(buf, xflags, prefix, sub);
+        prefix.truncate(l);
+    }
+}
+
+/// Converts a positional `arg_` to the type of `arg`.
+fn arg_value(xflags: &ast::XFlags, arg: &ast::Arg) -> String {
+    match &arg.val.ty {
+        ast::Ty::OsString => "arg_".to_string(),
+        ast::Ty::PathBuf => "arg_.into()".to_string(),
+        ast::Ty::FromStr(_) => {
+            let (m, ty) = (arg.val.method(xflags, "value_from_str"), arg.val.spanned(xflags));
+            format!("p_.{m}::<{ty}>(\"{}\", \"{}\", arg_)?", arg.val.name, arg.val.ty.name())
+        }
+    }
+}
+
+/// Checks the number of occurrences of everything on the path to the current
+/// command up front, so that all missing items are reported at once.
+fn emit_check_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
+    let mut checks = String::new();
+    for flag in &cmd.flags {
+        if flag.is_help() {
+            continue;
+        }
+        let mut usage = format!("--{}", flag.name);
+        if let Some(hint) = flag.value_hint() {
+            w!(usage, " {hint}");
+        }
+        emit_check(&mut checks, &usage, flag.arity, format!("{}.len()", flag.local(prefix)));
+    }
+    for arg in &cmd.args {
+        let (l, r) = ast::Arity::Required.brackets();
+        let r = if matches!(arg.arity, ast::Arity::Repeated { .. }) { ">..." } else { r };
+        let usage = format!("{l}{}{r}", arg.val.name);
+        emit_check(&mut checks, &usage, arg.arity, format!("{}.1.len()", arg.local(prefix)));
+    }
+    if !checks.is_empty() {
+        if cmd.idx == 0 {
+            buf.push_str(&checks);
+        } else {
+            w!(buf, "if matches!(state_, ");
+            emit_all_ids(buf, cmd);
+            w!(buf, ") {{\n{checks}}}\n");
+        }
+    }
+    if cmd.has_subcommands() && cmd.default_subcommand().is_none() {
+        w!(buf, "if state_ == {} {{ p_.subcommand_required(); }}\n", cmd.idx);
+    }
     for sub in &cmd.subcommands {
         let l = sub.push_prefix(prefix);
-        emit_match_arg_rec(buf, prefix, sub);
+        emit_check_rec(buf, prefix, sub);
         prefix.truncate(l);
     }
 }
 
-fn emit_record_rec(buf: &mut String, prefix: &mut String, cmd: &ast::Cmd) {
+fn emit_check(buf: &mut String, usage: &str, arity: ast::Arity, count: String) {
+    let (min, max) = match arity {
+        ast::Arity::Optional => (0, Some(1)),
+        ast::Arity::Required => (1, Some(1)),
+        ast::Arity::Repeated { min: 0, max: None } => return,
+        ast::Arity::Repeated { min, max } => (min, max),
+    };
+    w!(buf, "p_.check_count(\"{usage}\", {count}, {min}, {max:?});\n");
+}
+
+fn emit_record_rec(buf: &mut String, xflags: &ast::XFlags, prefix: &mut String, cmd: &ast::Cmd) {
     w!(buf, "{} {{\n", cmd.ident());
 
+    // Counts are already checked by `finish_checks`, so this can't fail.
     for flag in &cmd.flags {
         if flag.is_help() {
             continue;
         }
-        w!(buf, "{}: ", flag.ident());
-        match &flag.val {
-            Some(_val) => match flag.arity {
-                ast::Arity::Optional => {
-                    w!(buf, "p_.optional(\"--{}\", {prefix}{})?", flag.name, flag.ident())
+        let local = flag.local(prefix);
+        let val = match &flag.val {
+            Some(_val) => match (flag.arity, flag.delimiter) {
+                (ast::Arity::Optional, None) => format!("{local}.pop()"),
+                (ast::Arity::Optional, Some(_)) => format!("{local}.pop().unwrap_or_default()"),
+                (ast::Arity::Required, _) => format!("{local}.pop().unwrap()"),
+                (ast::Arity::Repeated { .. }, None) => local,
+                (ast::Arity::Repeated { .. }, Some(_)) => {
+                    format!("{local}.into_iter().flatten().collect()")
                 }
-                ast::Arity::Required => {
-                    w!(buf, "p_.required(\"--{}\", {prefix}{})?", flag.name, flag.ident())
-                }
-                ast::Arity::Repeated => w!(buf, "{prefix}{}", flag.ident()),
             },
             None => match flag.arity {
-                ast::Arity::Optional => {
-                    w!(buf, "p_.optional(\"--{}\", {prefix}{})?.is_some()", flag.name, flag.ident())
-                }
-                ast::Arity::Required => {
-                    w!(buf, "p_.required(\"--{}\", {prefix}{})?", flag.name, flag.ident())
-                }
-                ast::Arity::Repeated => w!(buf, "{prefix}{}.len() as u32", flag.ident()),
+                ast::Arity::Optional => format!("!{local}.is_empty()"),
+                ast::Arity::Required => "()".to_string(),
+                ast::Arity::Repeated { .. } => format!("{local}.len() as u32"),
             },
+        };
+        if val == flag.field() {
+            w!(buf, "{val},\n");
+        } else {
+            w!(buf, "{}: {val},\n", flag.field());
         }
-        w!(buf, ",\n");
     }
     for arg in &cmd.args {
-        let val = &arg.val;
-        w!(buf, "{}: ", val.ident());
+        let local = arg.local(prefix);
+        w!(buf, "{}: ", arg.field());
         match arg.arity {
-            ast::Arity::Optional => {
-                w!(buf, "p_.optional(\"{}\", {prefix}{}.1)?", val.name, val.ident())
-            }
-            ast::Arity::Required => {
-                w!(buf, "p_.required(\"{}\", {prefix}{}.1)?", val.name, val.ident())
-            }
-            ast::Arity::Repeated => w!(buf, "{prefix}{}.1", val.ident()),
+            ast::Arity::Optional => w!(buf, "{local}.1.pop()"),
+            ast::Arity::Required => w!(buf, "{local}.1.pop().unwrap()"),
+            ast::Arity::Repeated { .. } => w!(buf, "{local}.1"),
         }
         w!(buf, ",\n");
     }
     if cmd.has_subcommands() {
-        w!(buf, "subcommand: match state_ {{\n");
+        w!(buf, "{}: match state_ {{\n", raw(&xflags.subcommand_field));
         for sub in &cmd.subcommands {
             emit_leaf_ids_rec(buf, sub);
-            w!(buf, " => {}::{}(", cmd.cmd_enum_ident(), sub.ident());
+            w!(buf, " => {}::{}(", cmd.cmd_enum_ident(xflags), sub.ident());
             let l = prefix.len();
             prefix.push_str(&snake(&sub.name));
             prefix.push_str("__");
-            emit_record_rec(buf, prefix, sub);
+            emit_record_rec(buf, xflags, prefix, sub);
             prefix.truncate(l);
             w!(buf, "),\n");
         }
-        w!(buf, "_ => return Err(p_.subcommand_required())");
+        w!(buf, "_ => unreachable!(),\n");
         w!(buf, "}}\n");
     }
 
     w!(buf, "}}");
 }
 
+fn emit_unparse_rec(buf: &mut String, xflags: &ast::XFlags, cmd: &ast::Cmd) {
+    let flags = cmd.flags.iter().filter(|it| !it.is_help()).collect::<Vec<_>>();
+    let w =
+        if cmd.args.is_empty() && flags.is_empty() && !cmd.has_subcommands() { "_w" } else { "w_" };
+    w!(buf, "impl {} {{\n", cmd.ident());
+    w!(buf, "fn unparse_(&self, {w}: &mut xflags::rt::Unparser) {{\n");
+    for flag in flags {
+        let (field, name) = (flag.field(), &flag.name);
+        let Some(val) = &flag.val else {
+            match flag.arity {
+                ast::Arity::Optional => w!(buf, "if self.{field} {{ w_.flag(\"--{name}\"); }}\n"),
+                ast::Arity::Required => w!(buf, "w_.flag(\"--{name}\");\n"),
+                ast::Arity::Repeated { .. } => {
+                    w!(buf, "for _ in 0..self.{field} {{ w_.flag(\"--{name}\"); }}\n")
+                }
+            }
+            continue;
+        };
+        if let Some(d) = flag.delimiter {
+            let vals = match &val.ty {
+                ast::Ty::OsString | ast::Ty::PathBuf => format!("self.{field}.iter()"),
+                ast::Ty::FromStr(ty) if ty == "String" => format!("self.{field}.iter()"),
+                ast::Ty::FromStr(_) => {
+                    let m = val.method(xflags, "to_string");
+                    format!("self.{field}.iter().map(|it| it.{m}())")
+                }
+            };
+            w!(buf, "if !self.{field}.is_empty() {{\n");
+            w!(buf, "w_.flag(\"--{name}\");\n");
+            w!(buf, "w_.values({vals}, {d:?});\n");
+            w!(buf, "}}\n");
+            continue;
+        }
+        let value = unparse_value(xflags, val, "it");
+        match flag.arity {
+            ast::Arity::Optional => w!(
+                buf,
+                "if let Some(it) = &self.{field} {{ w_.flag(\"--{name}\"); w_.value({value}); }}\n"
+            ),
+            ast::Arity::Required => {
+                let value = unparse_value(xflags, val, &format!("&self.{field}"));
+                w!(buf, "w_.flag(\"--{name}\");\n");
+                w!(buf, "w_.value({value});\n");
+            }
+            ast::Arity::Repeated { .. } => {
+                w!(buf, "for it in &self.{field} {{ w_.flag(\"--{name}\"); w_.value({value}); }}\n")
+            }
+        }
+    }
+    let help = cmd.has_help_command();
+    for arg in &cmd.args {
+        let field = arg.field();
+        let value = unparse_value(xflags, &arg.val, "it");
+        match arg.arity {
+            ast::Arity::Optional => {
+                w!(buf, "if let Some(it) = &self.{field} {{ w_.arg({value}, {help}); }}\n")
+            }
+            ast::Arity::Required => {
+                let value = unparse_value(xflags, &arg.val, &format!("&self.{field}"));
+                w!(buf, "w_.arg({value}, {help});\n")
+            }
+            ast::Arity::Repeated { .. } => {
+                w!(buf, "for it in &self.{field} {{ w_.arg({value}, {help}); }}\n")
+            }
+        }
+    }
+    if cmd.has_subcommands() {
+        w!(buf, "match &self.{} {{\n", raw(&xflags.subcommand_field));
+        for sub in &cmd.subcommands {
+            w!(buf, "{}::{}(it) => {{\n", cmd.cmd_enum_ident(xflags), sub.ident());
+            // The default subcommand is selected implicitly.
+            if cmd.default_subcommand().map(|it| it.idx) != Some(sub.idx) {
+                w!(buf, "w_.subcommand(\"{}\");\n", sub.name);
+            }
+            w!(buf, "it.unparse_(w_);\n");
+            w!(buf, "}}\n");
+        }
+        w!(buf, "}}\n");
+    }
+    w!(buf, "}}\n");
+    w!(buf, "}}\n");
+
+    for sub in &cmd.subcommands {
+        emit_unparse_rec(buf, xflags, sub);
+    }
+}
+
+fn unparse_value(xflags: &ast::XFlags, val: &ast::Val, expr: &str) -> String {
+    match &val.ty {
+        ast::Ty::OsString | ast::Ty::PathBuf => expr.to_string(),
+        ast::Ty::FromStr(ty) if ty == "String" => expr.to_string(),
+        ast::Ty::FromStr(_) => {
+            format!("{}.{}()", expr.trim_start_matches('&'), val.method(xflags, "to_string"))
+        }
+    }
+}
+
 fn emit_leaf_ids_rec(buf: &mut String, cmd: &ast::Cmd) {
     if cmd.has_subcommands() {
         for sub in &cmd.subcommands {
@@ -381,10 +771,13 @@ fn emit_leaf_ids_rec(buf: &mut String, cmd: &ast::Cmd) {
     }
 }
 
-fn emit_all_ids_rec(buf: &mut String, cmd: &ast::Cmd) {
-    w!(buf, "| {}", cmd.idx);
-    for sub in &cmd.subcommands {
-        emit_all_ids_rec(buf, sub)
+fn emit_all_ids(buf: &mut String, cmd: &ast::Cmd) {
+    // Commands are numbered in pre-order, so the ids of a subtree are contiguous.
+    let last = cmd.last_idx();
+    if last == cmd.idx {
+        w!(buf, "{}", cmd.idx)
+    } else {
+        w!(buf, "{}..={last}", cmd.idx)
     }
 }
 
@@ -400,35 +793,35 @@ fn emit_default_transitions(buf: &mut String, cmd: &ast::Cmd) {
 fn emit_help(buf: &mut String, xflags: &ast::XFlags) {
     w!(buf, "impl {} {{\n", xflags.cmd.ident());
 
-    cmd_help_rec(buf, &xflags.cmd, "");
+    cmd_help_rec(buf, &xflags.cmd, "", &mut vec![xflags.program_name()]);
+    if !xflags.is_anon() {
+        w!(buf, "pub const SPEC_JSON: &'static str = {};\n", str_lit(&json::spec(xflags)));
+    }
 
     w!(buf, "}}\n");
 }
 
-fn cmd_help_rec(buf: &mut String, cmd: &ast::Cmd, prefix: &str) {
-    let mut help_buf = String::new();
-    w!(help_buf, "Usage: {}", cmd.name);
-    for arg in cmd.args_with_default() {
-        let (l, r) = arg.arity.brackets();
-        w!(help_buf, " {l}{}{r}", arg.val.name);
+fn cmd_help_rec<'a>(buf: &mut String, cmd: &'a ast::Cmd, prefix: &str, path: &mut Vec<&'a str>) {
+    let help = help_text(cmd, &path.join(" "), false);
+    let help_short = help_text(cmd, &path.join(" "), true);
+    // Constants of subcommands go before the ones of their parent.
+    for subcommand in &cmd.subcommands {
+        let prefix = format!("{}{}__", prefix, subcommand.name);
+        path.push(&subcommand.name);
+        cmd_help_rec(buf, subcommand, &prefix, path);
+        path.pop();
     }
-    for flag in cmd.flags_with_default() {
-        // <-f> doesn't make sense, if it has to be included it should just be -f
-        let (l, r) = match flag.arity {
-            ast::Arity::Required => ("", ""),
-            _ => flag.arity.brackets(),
-        };
-        let f = flag.short.clone().unwrap_or_else(|| format!("-{}", flag.name));
+    let name = snake(prefix).to_uppercase();
+    w!(buf, "const HELP_{name}: &'static str = {};\n", str_lit(&help));
+    w!(buf, "const HELP_SHORT_{name}: &'static str = {};\n", str_lit(&help_short));
+}
 
-        match &flag.val {
-            Some(v) => w!(help_buf, " {l}-{f} <{}>{r}", v.name),
-            None => w!(help_buf, " {l}-{f}{r}"),
-        }
-    }
-    if cmd.has_subcommands() {
-        w!(help_buf, " <COMMAND>")
-    }
+/// Renders `--help`, or `-h` if `short` is set, which keeps only the first
+/// paragraph of each doc comment.
+fn help_text(cmd: &ast::Cmd, path: &str, short: bool) -> String {
+    let mut help_buf = format!("Usage: {}", usage_line(cmd, path));
     if let Some(doc) = &cmd.doc {
+        let doc = if short { first_paragraph(doc) } else { doc.clone() };
         w!(help_buf, "\n\n{}\n", doc);
     }
     let args_with_default = cmd.args_with_default();
@@ -437,34 +830,121 @@ fn cmd_help_rec(buf: &mut String, cmd: &ast::Cmd, prefix: &str) {
         for arg in args_with_default {
             let (l, r) = arg.arity.brackets();
             let pre_doc = format!("{l}{}{r}", arg.val.name);
-            w!(help_buf, "  {:<20} {}\n", pre_doc, arg.doc.as_deref().unwrap_or(""));
+            help_item(&mut help_buf, &pre_doc, arg.doc.as_deref(), short);
         }
     }
     let flags_with_default = cmd.flags_with_default();
-    if !flags_with_default.is_empty() {
-        w!(help_buf, "\nOptions:\n");
-        for flag in flags_with_default {
-            let short = flag.short.as_ref().map(|it| format!("-{it}, ")).unwrap_or_default();
-            let value = flag.val.as_ref().map(|it| format!(" <{}>", it.name)).unwrap_or_default();
-            let pre_doc = format!("{short}--{}{value}", flag.name);
-            w!(help_buf, "  {:<20} {}\n", pre_doc, flag.doc.as_deref().unwrap_or(""));
+    let flag_sections = sections(flags_with_default, |it| it.section.as_deref());
+    let mut commands = sections(cmd.named_subcommands(), |it| it.section.as_deref());
+    for (section, flags) in &flag_sections {
+        if flags.is_empty() {
+            continue;
+        }
+        w!(help_buf, "\n{}:\n", section.unwrap_or("Options"));
+        for flag in flags {
+            let short_name = flag.short.as_ref().map(|it| format!("-{it}, ")).unwrap_or_default();
+            let value = flag.value_hint().map(|it| format!(" {it}")).unwrap_or_default();
+            let pre_doc = format!("{short_name}--{}{value}", flag.name);
+            help_item(&mut help_buf, &pre_doc, flag.doc.as_deref(), short);
+        }
+        // Subcommands in the same section are listed under the same heading.
+        if let Some(idx) = commands.iter().position(|(it, _)| it.is_some() && it == section) {
+            for subcommand in commands.remove(idx).1 {
+                let doc = subcommand.doc.as_deref().and_then(|it| it.lines().next());
+                w!(help_buf, "  {:<20} {}\n", subcommand.name, doc.unwrap_or(""));
+            }
         }
     }
-    w!(help_buf, "\nCommands:");
-    for subcommand in cmd.named_subcommands() {
-        w!(help_buf, "\n  {:<20} {}", subcommand.name, subcommand.doc.as_deref().unwrap_or(""));
+    if commands[0].1.is_empty() && !cmd.has_help_command() {
+        commands.remove(0);
     }
-    for subcommand in &cmd.subcommands {
-        let prefix = format!("{}{}__", prefix, subcommand.name);
-        cmd_help_rec(buf, subcommand, &prefix);
+    for (i, (section, subcommands)) in commands.iter().enumerate() {
+        if i > 0 {
+            w!(help_buf, "\n");
+        }
+        w!(help_buf, "\n{}:", section.unwrap_or("Commands"));
+        for subcommand in subcommands {
+            // Only the first line, to keep the listing aligned.
+            let doc = subcommand.doc.as_deref().and_then(|it| it.lines().next());
+            w!(help_buf, "\n  {:<20} {}", subcommand.name, doc.unwrap_or(""));
+        }
+        if section.is_none() && cmd.has_help_command() {
+            w!(help_buf, "\n  {:<20} ", "help");
+            w!(help_buf, "Print this message or the help of the given subcommand(s)");
+        }
     }
-    w!(help_buf, "\n  {:<20} ", "help");
-    w!(help_buf, "Print this message or the help of the given subcommand(s)");
-    w!(buf, "const HELP_{}: &'static str = \"{help_buf}\";\n", snake(prefix).to_uppercase());
+    // Sections end with a newline, the commands listing doesn't.
+    help_buf.truncate(help_buf.trim_end_matches('\n').len());
+    if short {
+        return help_buf;
+    }
+    if !cmd.examples.is_empty() {
+        w!(help_buf, "\n\nExamples:");
+        for example in &cmd.examples {
+            w!(help_buf, "\n  {}", example.cmdline);
+            for line in example.doc.iter().flat_map(|it| it.lines()) {
+                w!(help_buf, "\n      {line}");
+            }
+        }
+    }
+    if let Some(after_help) = &cmd.after_help {
+        w!(help_buf, "\n\n{after_help}");
+    }
+    help_buf
+}
+
+/// `name [args] [flags] [<COMMAND>]`, the first line of help and of errors.
+fn usage_line(cmd: &ast::Cmd, name: &str) -> String {
+    let mut buf = name.to_string();
+    for arg in cmd.args_with_default() {
+        let (l, r) = arg.arity.brackets();
+        w!(buf, " {l}{}{r}", arg.val.name);
+    }
+    for flag in cmd.flags_with_default() {
+        // <-f> doesn't make sense, if it has to be included it should just be -f
+        let (l, r) = match flag.arity {
+            ast::Arity::Required => ("", ""),
+            ast::Arity::Repeated { min, .. } if min > 0 => ("", "..."),
+            _ => flag.arity.brackets(),
+        };
+        let f = flag.short.clone().unwrap_or_else(|| format!("-{}", flag.name));
+
+        match flag.value_hint() {
+            Some(v) => w!(buf, " {l}-{f} {v}{r}"),
+            None => w!(buf, " {l}-{f}{r}"),
+        }
+    }
+    if cmd.has_subcommands() {
+        w!(buf, " <COMMAND>")
+    }
+    buf
+}
+
+fn help_item(help_buf: &mut String, pre_doc: &str, doc: Option<&str>, short: bool) {
+    let doc = doc.unwrap_or("");
+    if short {
+        let doc = first_paragraph(doc).split_whitespace().collect::<Vec<_>>().join(" ");
+        w!(help_buf, "  {:<20} {}\n", pre_doc, doc);
+        return;
+    }
+    let mut lines = doc.lines();
+    w!(help_buf, "  {:<20} {}\n", pre_doc, lines.next().unwrap_or(""));
+    // Continuation lines are aligned with the first one.
+    for line in lines {
+        if line.is_empty() {
+            w!(help_buf, "\n");
+        } else {
+            w!(help_buf, "  {:<20} {}\n", "", line);
+        }
+    }
+}
+
+fn first_paragraph(doc: &str) -> String {
+    doc.lines().take_while(|it| !it.trim().is_empty()).collect::<Vec<_>>().join("\n")
 }
 
 impl ast::Cmd {
-    fn ident(&self) -> String {
+    pub(crate) fn ident(&self) -> String {
         if self.name.is_empty() {
             return "Flags".to_string();
         }
@@ -473,8 +953,8 @@ impl ast::Cmd {
     pub(crate) fn all_identifiers(&self) -> impl Iterator<Item = &String> {
         [&self.name].into_iter().chain(self.aliases.iter())
     }
-    fn cmd_enum_ident(&self) -> String {
-        format!("{}Cmd", self.ident())
+    pub(crate) fn cmd_enum_ident(&self, xflags: &ast::XFlags) -> String {
+        format!("{}{}", self.ident(), xflags.enum_suffix)
     }
     fn push_prefix(&self, buf: &mut String) -> usize {
         let l = buf.len();
@@ -482,10 +962,18 @@ impl ast::Cmd {
         buf.push_str("__");
         l
     }
-    fn has_subcommands(&self) -> bool {
+    fn last_idx(&self) -> u8 {
+        self.subcommands.iter().map(|it| it.last_idx()).max().unwrap_or(self.idx)
+    }
+    pub(crate) fn has_subcommands(&self) -> bool {
         !self.subcommands.is_empty()
     }
-    fn named_subcommands(&self) -> &[ast::Cmd] {
+    /// Whether `help` is matched like a subcommand. It doesn't take precedence
+    /// over arguments of leaf commands.
+    pub(crate) fn has_help_command(&self) -> bool {
+        self.args.is_empty() || self.has_subcommands()
+    }
+    pub(crate) fn named_subcommands(&self) -> &[ast::Cmd] {
         let start = if self.default { 1 } else { 0 };
         &self.subcommands[start..]
     }
@@ -496,7 +984,14 @@ impl ast::Cmd {
             None
         }
     }
-    fn args_with_default(&self) -> Vec<&ast::Arg> {
+    /// Required arguments after a repeated one, filled from the end.
+    fn trailing_args(&self) -> &[ast::Arg] {
+        match self.args.iter().position(|it| matches!(it.arity, ast::Arity::Repeated { .. })) {
+            Some(idx) => &self.args[idx + 1..],
+            None => &[],
+        }
+    }
+    pub(crate) fn args_with_default(&self) -> Vec<&ast::Arg> {
         let mut res = self.args.iter().collect::<Vec<_>>();
         if let Some(sub) = self.default_subcommand() {
             res.extend(sub.args_with_default());
@@ -513,25 +1008,93 @@ impl ast::Cmd {
 }
 
 impl ast::Flag {
-    fn ident(&self) -> String {
-        snake(&self.name)
+    /// Name of the field, without escaping keywords.
+    pub(crate) fn ident(&self) -> String {
+        snake(self.field.as_deref().unwrap_or(&self.name))
+    }
+    fn field(&self) -> String {
+        raw(&self.ident())
+    }
+    /// Local variable holding the values while parsing.
+    fn local(&self, prefix: &str) -> String {
+        raw(&format!("{prefix}{}", self.ident()))
+    }
+    pub(crate) fn value_hint(&self) -> Option<String> {
+        let val = self.val.as_ref()?;
+        let res = match self.delimiter {
+            Some(d) => format!("<{}>{d}...", val.name),
+            None => format!("<{}>", val.name),
+        };
+        Some(res)
     }
 }
 
 impl ast::Arity {
-    fn brackets(&self) -> (&str, &str) {
+    pub(crate) fn brackets(&self) -> (&str, &str) {
         match self {
             ast::Arity::Optional => ("[", "]"),
             ast::Arity::Required => ("<", ">"),
-            ast::Arity::Repeated => ("[", "]..."),
+            ast::Arity::Repeated { min: 0, .. } => ("[", "]..."),
+            ast::Arity::Repeated { .. } => ("<", ">..."),
         }
     }
 }
 
+impl ast::Arg {
+    /// Name of the field, without escaping keywords.
+    pub(crate) fn ident(&self) -> String {
+        snake(self.field.as_deref().unwrap_or(&self.val.name))
+    }
+    fn field(&self) -> String {
+        raw(&self.ident())
+    }
+    /// Local variable holding the values while parsing.
+    fn local(&self, prefix: &str) -> String {
+        raw(&format!("{prefix}{}", self.ident()))
+    }
+}
+
 impl ast::Val {
-    fn ident(&self) -> String {
-        snake(&self.name)
+    fn spanned(&self, xflags: &ast::XFlags) -> String {
+        spanned(xflags, self.ty.name(), self.span)
+    }
+    /// A method with trait bounds on the type, named with the span of the
+    /// type, so that unsatisfied bounds are reported at the DSL.
+    fn method(&self, xflags: &ast::XFlags, name: &str) -> String {
+        spanned(xflags, name, self.span)
+    }
+}
+
+impl ast::Ty {
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            ast::Ty::PathBuf => "PathBuf",
+            ast::Ty::OsString => "OsString",
+            ast::Ty::FromStr(it) => it,
+        }
+    }
+}
+
+/// Groups items by their section, in the order of first appearance. The first
+/// group, possibly empty, holds items without a section.
+pub(crate) fn sections<'a, T>(
+    items: impl IntoIterator<Item = &'a T>,
+    section: impl Fn(&'a T) -> Option<&'a str>,
+) -> Vec<(Option<&'a str>, Vec<&'a T>)> {
+    let mut res = vec![(None, Vec::new())];
+    for item in items {
+        let title = section(item);
+        match res.iter_mut().find(|(it, _)| *it == title) {
+            Some((_, group)) => group.push(item),
+            None => res.push((title, vec![item])),
+        }
     }
+    res
+}
+
+/// Quotes a string as a Rust literal, keeping newlines as is.
+fn str_lit(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
 }
 
 fn blank_line(buf: &mut String) {
@@ -551,6 +1114,46 @@ fn first_upper(s: &str) -> String {
         .collect()
 }
 
+/// Refers to a token of the DSL from the generated code, see
+/// [`ast::XFlags::spans`].
+fn spanned(xflags: &ast::XFlags, text: &str, span: Span) -> String {
+    let mut spans = xflags.spans.borrow_mut();
+    let res = format!("{SPAN_PLACEHOLDER}{}", spans.len());
+    spans.push((text.to_string(), span));
+    res
+}
+
+pub(crate) const SPAN_PLACEHOLDER: &str = "__xflags_span_";
+
+/// Replaces the placeholders of [`spanned`] with plain text.
+pub(crate) fn unspanned(xflags: &ast::XFlags, text: &str) -> String {
+    let mut res = text.to_string();
+    // Go in reverse, so that `_1` doesn't match a prefix of `_10`.
+    for (i, (text, _)) in xflags.spans.borrow().iter().enumerate().rev() {
+        res = res.replace(&format!("{SPAN_PLACEHOLDER}{i}"), text);
+    }
+    res
+}
+
+/// Keywords which can be used as raw identifiers. `self`, `super`, `crate`
+/// and `Self` can't.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Escapes keywords, so that `--type` becomes the `r#type` field.
+fn raw(ident: &str) -> String {
+    if KEYWORDS.contains(&ident) {
+        format!("r#{ident}")
+    } else {
+        ident.to_string()
+    }
+}
+
 fn snake(s: &str) -> String {
     s.replace('-', "_")
 }
@@ -617,6 +1220,15 @@ use std::{{ffi::OsString, path::PathBuf}};
             let name = entry.file_name();
             did_update |= update_on_disk_if_different(&test_dir.join("it").join(name), code);
 
+            let xflags = crate::parse::xflags(text.parse().unwrap()).unwrap();
+            if let Some(path) = &xflags.markdown {
+                let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+                let old = fs::read_to_string(&path).unwrap_or_default();
+                let reference = crate::markdown::render(&xflags);
+                let new = crate::update::with_reference(&old, &reference).unwrap();
+                did_update |= update_on_disk_if_different(&path, new);
+            }
+
             if fmt.is_none() {
                 panic!("syntax error");
             }
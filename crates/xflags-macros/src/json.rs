@@ -0,0 +1,159 @@
+//! Machine-readable description of the command tree, for tools like docs
+//! generators and shell completions which would otherwise scrape `--help`.
+
+use std::fmt::Write;
+
+use crate::ast;
+
+pub(crate) fn spec(xflags: &ast::XFlags) -> String {
+    let mut buf = String::new();
+    cmd(&xflags.cmd, false).render(&mut buf, 0);
+    buf
+}
+
+enum Json {
+    Null,
+    Bool(bool),
+    Num(usize),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(&'static str, Json)>),
+}
+
+fn cmd(cmd: &ast::Cmd, default: bool) -> Json {
+    let subcommands = cmd
+        .subcommands
+        .iter()
+        .enumerate()
+        .map(|(i, sub)| self::cmd(sub, cmd.default && i == 0))
+        .collect();
+    Json::Obj(vec![
+        ("name", Json::Str(cmd.name.clone())),
+        ("aliases", Json::Arr(cmd.aliases.iter().cloned().map(Json::Str).collect())),
+        ("default", Json::Bool(default)),
+        ("doc", opt_str(cmd.doc.as_deref())),
+        ("section", opt_str(cmd.section.as_deref())),
+        ("args", Json::Arr(cmd.args.iter().map(arg).collect())),
+        ("flags", Json::Arr(cmd.flags.iter().map(flag).collect())),
+        ("subcommands", Json::Arr(subcommands)),
+        ("examples", Json::Arr(cmd.examples.iter().map(example).collect())),
+        ("after_help", opt_str(cmd.after_help.as_deref())),
+    ])
+}
+
+fn example(example: &ast::Example) -> Json {
+    Json::Obj(vec![
+        ("command", Json::Str(example.cmdline.clone())),
+        ("doc", opt_str(example.doc.as_deref())),
+    ])
+}
+
+fn arg(arg: &ast::Arg) -> Json {
+    let mut fields = vec![("name", Json::Str(arg.val.name.clone()))];
+    fields.extend(arity(arg.arity));
+    fields.push(("type", Json::Str(arg.val.ty.name().to_string())));
+    fields.push(("doc", opt_str(arg.doc.as_deref())));
+    Json::Obj(fields)
+}
+
+fn flag(flag: &ast::Flag) -> Json {
+    let mut fields =
+        vec![("long", Json::Str(flag.name.clone())), ("short", opt_str(flag.short.as_deref()))];
+    fields.extend(arity(flag.arity));
+    let value = match &flag.val {
+        Some(val) => Json::Obj(vec![
+            ("name", Json::Str(val.name.clone())),
+            ("type", Json::Str(val.ty.name().to_string())),
+        ]),
+        None => Json::Null,
+    };
+    fields.push(("value", value));
+    fields.push(("delimiter", opt_str(flag.delimiter.map(String::from).as_deref())));
+    fields.push(("doc", opt_str(flag.doc.as_deref())));
+    fields.push(("section", opt_str(flag.section.as_deref())));
+    Json::Obj(fields)
+}
+
+fn arity(arity: ast::Arity) -> [(&'static str, Json); 3] {
+    let (name, min, max) = match arity {
+        ast::Arity::Optional => ("optional", 0, Some(1)),
+        ast::Arity::Required => ("required", 1, Some(1)),
+        ast::Arity::Repeated { min, max } => ("repeated", min, max),
+    };
+    [
+        ("arity", Json::Str(name.to_string())),
+        ("min", Json::Num(min)),
+        ("max", max.map_or(Json::Null, Json::Num)),
+    ]
+}
+
+fn opt_str(s: Option<&str>) -> Json {
+    s.map_or(Json::Null, |it| Json::Str(it.to_string()))
+}
+
+impl Json {
+    fn render(&self, buf: &mut String, indent: usize) {
+        match self {
+            Json::Null => buf.push_str("null"),
+            Json::Bool(it) => {
+                let _ = write!(buf, "{it}");
+            }
+            Json::Num(it) => {
+                let _ = write!(buf, "{it}");
+            }
+            Json::Str(it) => escape(buf, it),
+            Json::Arr(items) if items.is_empty() => buf.push_str("[]"),
+            Json::Arr(items) => {
+                buf.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        buf.push(',');
+                    }
+                    newline(buf, indent + 1);
+                    item.render(buf, indent + 1);
+                }
+                newline(buf, indent);
+                buf.push(']');
+            }
+            Json::Obj(fields) => {
+                buf.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        buf.push(',');
+                    }
+                    newline(buf, indent + 1);
+                    escape(buf, key);
+                    buf.push_str(": ");
+                    value.render(buf, indent + 1);
+                }
+                newline(buf, indent);
+                buf.push('}');
+            }
+        }
+    }
+}
+
+fn newline(buf: &mut String, indent: usize) {
+    buf.push('\n');
+    for _ in 0..indent {
+        buf.push_str("  ");
+    }
+}
+
+fn escape(buf: &mut String, s: &str) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
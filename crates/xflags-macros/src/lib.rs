@@ -1,6 +1,8 @@
 mod ast;
 mod parse;
 mod emit;
+mod json;
+mod markdown;
 mod update;
 
 #[proc_macro]
@@ -8,11 +10,10 @@ pub fn xflags(_ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Stub out the code, but let rust-analyzer resolve the invocation
     #[cfg(not(test))]
     {
-        let text = match parse::xflags(_ts) {
-            Ok(cmd) => emit::emit(&cmd),
-            Err(err) => format!("compile_error!(\"invalid flags syntax, {err}\");"),
-        };
-        text.parse().unwrap()
+        match parse::xflags(_ts) {
+            Ok(xflags) => respan(emit::emit(&xflags).parse().unwrap(), &xflags),
+            Err(errors) => compile_errors(errors),
+        }
     }
     #[cfg(test)]
     unimplemented!()
@@ -23,11 +24,15 @@ pub fn parse_or_exit(_ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Stub out the code, but let rust-analyzer resolve the invocation
     #[cfg(not(test))]
     {
-        let text = match parse::parse_or_exit(_ts) {
-            Ok(cmd) => emit::emit(&cmd),
-            Err(err) => format!("compile_error!(\"invalid flags syntax, {err}\")"),
-        };
-        text.parse().unwrap()
+        match parse::parse_or_exit(_ts) {
+            Ok(xflags) => respan(emit::emit(&xflags).parse().unwrap(), &xflags),
+            // `parse_or_exit!` is an expression, so wrap the errors in a block.
+            Err(errors) => {
+                let group =
+                    proc_macro::Group::new(proc_macro::Delimiter::Brace, compile_errors(errors));
+                proc_macro::TokenTree::Group(group).into()
+            }
+        }
     }
     #[cfg(test)]
     {
@@ -36,11 +41,72 @@ pub fn parse_or_exit(_ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
     }
 }
 
+/// Replaces the placeholders left by `emit` with the original DSL tokens.
+#[cfg(not(test))]
+fn respan(ts: proc_macro::TokenStream, xflags: &ast::XFlags) -> proc_macro::TokenStream {
+    use proc_macro::{Group, Ident, TokenTree};
+
+    ts.into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(g) => {
+                let mut res = Group::new(g.delimiter(), respan(g.stream(), xflags));
+                res.set_span(g.span());
+                TokenTree::Group(res)
+            }
+            TokenTree::Ident(ident) => {
+                let text = ident.to_string();
+                match text
+                    .strip_prefix(emit::SPAN_PLACEHOLDER)
+                    .and_then(|it| it.parse::<usize>().ok())
+                {
+                    Some(idx) => {
+                        let (text, span) = &xflags.spans.borrow()[idx];
+                        let ident = match text.strip_prefix("r#") {
+                            Some(it) => Ident::new_raw(it, *span),
+                            None => Ident::new(text, *span),
+                        };
+                        TokenTree::Ident(ident)
+                    }
+                    None => TokenTree::Ident(ident),
+                }
+            }
+            tt => tt,
+        })
+        .collect()
+}
+
+/// `compile_error!("...");` for each error, pointing at the offending DSL
+/// tokens.
+#[cfg(not(test))]
+fn compile_errors(errors: Vec<parse::Error>) -> proc_macro::TokenStream {
+    use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, TokenStream, TokenTree};
+
+    let mut res = TokenStream::new();
+    for err in errors {
+        let span = err.span();
+        let mut msg = Literal::string(&format!("invalid flags syntax, {err}"));
+        msg.set_span(span);
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(msg).into());
+        args.set_span(span);
+        let tokens: [TokenTree; 4] = [
+            Ident::new("compile_error", span).into(),
+            Punct::new('!', Spacing::Alone).into(),
+            args.into(),
+            Punct::new(';', Spacing::Alone).into(),
+        ];
+        for mut tt in tokens {
+            tt.set_span(span);
+            res.extend([tt]);
+        }
+    }
+    res
+}
+
 #[cfg(test)]
 pub fn compile(src: &str) -> String {
     use proc_macro2::TokenStream;
 
     let ts = src.parse::<TokenStream>().unwrap();
-    let cmd = parse::xflags(ts).unwrap();
-    emit::emit(&cmd)
+    let xflags = parse::xflags(ts).unwrap();
+    emit::unspanned(&xflags, &emit::emit(&xflags))
 }
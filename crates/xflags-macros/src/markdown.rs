@@ -0,0 +1,116 @@
+//! Markdown rendering of the command tree, kept in sync with the DSL by
+//! `UPDATE_XFLAGS`, like the generated code.
+
+use std::fmt::Write;
+
+use crate::{ast, emit::sections};
+
+macro_rules! w {
+    ($($tt:tt)*) => {{ let _ = write!($($tt)*); }};
+}
+
+pub(crate) fn render(xflags: &ast::XFlags) -> String {
+    let mut buf = String::new();
+    cmd_rec(&mut buf, &xflags.cmd, &mut Vec::new(), false);
+    buf.truncate(buf.trim_end().len());
+    buf.push('\n');
+    buf
+}
+
+fn cmd_rec<'a>(buf: &mut String, cmd: &'a ast::Cmd, path: &mut Vec<&'a str>, default: bool) {
+    path.push(&cmd.name);
+    w!(buf, "### `{}`", path.join(" "));
+    if default {
+        w!(buf, " (default)");
+    }
+    w!(buf, "\n\n");
+    if let Some(doc) = &cmd.doc {
+        w!(buf, "{doc}\n\n");
+    }
+
+    if !cmd.args.is_empty() {
+        w!(buf, "| Argument | Description |\n| --- | --- |\n");
+        for arg in &cmd.args {
+            let (l, r) = arg.arity.brackets();
+            let doc = cell(arg.doc.as_deref());
+            w!(buf, "| `{l}{}{r}` | {doc} |\n", arg.val.name);
+        }
+        w!(buf, "\n");
+    }
+
+    // Groups without a heading come first, so that they aren't taken for a
+    // part of the section above them. Switches and subcommands sharing a
+    // section go under one heading.
+    let mut commands = sections(cmd.named_subcommands(), |it| it.section.as_deref());
+    for (section, flags) in sections(&cmd.flags, |it| it.section.as_deref()) {
+        let Some(section) = section else {
+            flags_table(buf, &flags);
+            commands_table(buf, &commands.remove(0).1);
+            continue;
+        };
+        w!(buf, "{section}:\n\n");
+        flags_table(buf, &flags);
+        if let Some(idx) = commands.iter().position(|(it, _)| *it == Some(section)) {
+            commands_table(buf, &commands.remove(idx).1);
+        }
+    }
+    for (section, subcommands) in commands {
+        if let Some(section) = section {
+            w!(buf, "{section}:\n\n");
+        }
+        commands_table(buf, &subcommands);
+    }
+
+    if !cmd.examples.is_empty() {
+        w!(buf, "Examples:\n\n");
+        for example in &cmd.examples {
+            w!(buf, "- `{}`", example.cmdline);
+            if let Some(doc) = &example.doc {
+                w!(buf, ": {}", cell(Some(doc)));
+            }
+            w!(buf, "\n");
+        }
+        w!(buf, "\n");
+    }
+
+    if let Some(after_help) = &cmd.after_help {
+        w!(buf, "{after_help}\n\n");
+    }
+
+    for (i, sub) in cmd.subcommands.iter().enumerate() {
+        cmd_rec(buf, sub, path, cmd.default && i == 0);
+    }
+    path.pop();
+}
+
+/// Table cells must fit on a single line.
+fn flags_table(buf: &mut String, flags: &[&ast::Flag]) {
+    if flags.is_empty() {
+        return;
+    }
+    w!(buf, "| Option | Description |\n| --- | --- |\n");
+    for flag in flags {
+        let short = flag.short.as_ref().map(|it| format!("`-{it}`, ")).unwrap_or_default();
+        let value = flag.value_hint().map(|it| format!(" {it}")).unwrap_or_default();
+        let doc = cell(flag.doc.as_deref());
+        w!(buf, "| {short}`--{}{value}` | {doc} |\n", flag.name);
+    }
+    w!(buf, "\n");
+}
+
+fn commands_table(buf: &mut String, subcommands: &[&ast::Cmd]) {
+    if subcommands.is_empty() {
+        return;
+    }
+    w!(buf, "| Command | Description |\n| --- | --- |\n");
+    for sub in subcommands {
+        let summary = sub.doc.as_deref().and_then(|it| it.lines().next());
+        w!(buf, "| `{}` | {} |\n", sub.name, cell(summary));
+    }
+    w!(buf, "\n");
+}
+
+fn cell(doc: Option<&str>) -> String {
+    let doc = doc.unwrap_or_default();
+    doc.split_whitespace().collect::<Vec<_>>().join(" ").replace('|', "\\|")
+}
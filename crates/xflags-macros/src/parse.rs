@@ -1,9 +1,9 @@
-use std::{fmt, mem};
+use std::{collections::HashSet, fmt, mem};
 
 #[cfg(not(test))]
-use proc_macro::{Delimiter, TokenStream, TokenTree};
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
 #[cfg(test)]
-use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
 
 use crate::ast;
 
@@ -12,6 +12,19 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 #[derive(Debug)]
 pub(crate) struct Error {
     msg: String,
+    span: Option<Span>,
+}
+
+impl Error {
+    pub(crate) fn span(&self) -> Span {
+        self.span.unwrap_or_else(Span::call_site)
+    }
+
+    /// Points the error at `span`, instead of the token the parser stopped at.
+    fn at(mut self, span: Span) -> Error {
+        self.span = Some(span);
+        self
+    }
 }
 
 impl std::error::Error for Error {}
@@ -22,23 +35,91 @@ impl fmt::Display for Error {
     }
 }
 
-pub(crate) fn xflags(ts: TokenStream) -> Result<ast::XFlags> {
+pub(crate) fn xflags(ts: TokenStream) -> Result<ast::XFlags, Vec<Error>> {
     let p = &mut Parser::new(ts);
-    let src = if p.eat_keyword("src") { Some(p.expect_string()?) } else { None };
-    let doc = opt_doc(p)?;
+    let res = xflags_impl(p);
+    p.finish(res)
+}
+
+const OPTIONS: &[&str] =
+    &["src", "markdown", "derive", "subcommand_field", "enum_suffix", "program", "to_args"];
+
+fn xflags_impl(p: &mut Parser) -> Result<ast::XFlags> {
+    let mut src = None;
+    let mut markdown = None;
+    let mut derives = None;
+    let mut subcommand_field = None;
+    let mut enum_suffix = None;
+    let mut program = None;
+    let mut to_args = false;
+    // Top-level options can go in any order, but only once each.
+    while let Some(kw) = OPTIONS.iter().copied().find(|&kw| p.at_keyword(kw)) {
+        let span = p.span();
+        p.bump();
+        let duplicate = match kw {
+            "src" => src.replace(p.expect_string()?).is_some(),
+            "markdown" => markdown.replace(p.expect_string()?).is_some(),
+            "derive" => derives.replace(derive_list(p)?).is_some(),
+            "subcommand_field" => subcommand_field.replace(p.expect_ident()?).is_some(),
+            "enum_suffix" => enum_suffix.replace(p.expect_ident()?).is_some(),
+            "program" => program.replace(p.expect_string()?).is_some(),
+            "to_args" => mem::replace(&mut to_args, true),
+            _ => unreachable!(),
+        };
+        if duplicate {
+            p.error(span, format!("`{kw}` is specified multiple times"));
+        }
+    }
+    let derives = derives.unwrap_or_else(|| vec!["Debug".to_string()]);
+    let subcommand_field = subcommand_field.unwrap_or_else(|| "subcommand".to_string());
+    let enum_suffix = enum_suffix.unwrap_or_else(|| "Cmd".to_string());
+    let attrs = attrs(p)?;
+    let vis = vis(p)?;
     let mut cmd = cmd(p)?;
-    cmd.doc = doc;
+    attrs.apply_to_cmd(&mut cmd);
     add_help(&mut cmd);
-    let res = ast::XFlags { src, cmd };
+    let res = ast::XFlags {
+        src,
+        markdown,
+        derives,
+        vis,
+        subcommand_field,
+        enum_suffix,
+        program,
+        to_args,
+        cmd,
+        spans: Default::default(),
+    };
+    check_names(p, &res);
+    check_positionals_rec(p, &res.cmd, false);
     Ok(res)
 }
 
-pub(crate) fn parse_or_exit(ts: TokenStream) -> Result<ast::XFlags> {
+pub(crate) fn parse_or_exit(ts: TokenStream) -> Result<ast::XFlags, Vec<Error>> {
     let p = &mut Parser::new(ts);
+    let res = parse_or_exit_impl(p);
+    p.finish(res)
+}
+
+fn parse_or_exit_impl(p: &mut Parser) -> Result<ast::XFlags> {
+    let program = if p.eat_keyword("program") { Some(p.expect_string()?) } else { None };
     let mut cmd = anon_cmd(p)?;
     assert!(cmd.subcommands.is_empty());
     add_help(&mut cmd);
-    let res = ast::XFlags { src: None, cmd };
+    let res = ast::XFlags {
+        src: None,
+        markdown: None,
+        derives: vec!["Debug".to_string()],
+        vis: "pub".to_string(),
+        subcommand_field: "subcommand".to_string(),
+        enum_suffix: "Cmd".to_string(),
+        program,
+        to_args: false,
+        cmd,
+        spans: Default::default(),
+    };
+    check_names(p, &res);
+    check_positionals_rec(p, &res.cmd, false);
     Ok(res)
 }
 
@@ -49,13 +130,149 @@ fn add_help(cmd: &mut ast::Cmd) {
         short: Some("h".to_string()),
         doc: Some("Prints help".to_string()),
         val: None,
+        delimiter: None,
+        field: None,
+        section: None,
+        attrs: Vec::new(),
+        span: Span::call_site(),
     };
     cmd.flags.push(help);
 }
 
+/// Checks for names which would make parts of the generated code unreachable
+/// or ill-formed.
+fn check_names(p: &mut Parser, xflags: &ast::XFlags) {
+    check_switches_rec(p, &xflags.cmd, &mut Vec::new());
+    check_fields_rec(p, xflags, &xflags.cmd);
+    check_types_rec(p, xflags, &xflags.cmd, &mut HashSet::new());
+}
+
+/// Switches of a command are also accepted by its subcommands, so they can't
+/// be redefined further down the tree.
+fn check_switches_rec<'a>(
+    p: &mut Parser,
+    cmd: &'a ast::Cmd,
+    defined: &mut Vec<(String, &'a ast::Flag, &'a ast::Cmd)>,
+) {
+    let len = defined.len();
+    // Check the user's switches against `--help` and `-h`, not the other way around.
+    let flags = cmd.flags.iter().filter(|it| it.is_help());
+    for flag in flags.chain(cmd.flags.iter().filter(|it| !it.is_help())) {
+        let long = format!("--{}", flag.name);
+        let short = flag.short.as_ref().map(|it| format!("-{it}"));
+        for name in [Some(long), short].into_iter().flatten() {
+            match defined.iter().find(|(it, _, _)| *it == name) {
+                Some((_, prev, _)) if prev.is_help() => {
+                    p.error(flag.span, format!("`{name}` is reserved for help"))
+                }
+                Some((_, _, owner)) if owner.idx == cmd.idx => {
+                    p.error(flag.span, format!("`{name}` is defined multiple times"))
+                }
+                Some((_, _, owner)) => p.error(
+                    flag.span,
+                    format!("`{name}` is already defined by the parent command `{}`", owner.name),
+                ),
+                None => defined.push((name, flag, cmd)),
+            }
+        }
+    }
+    for sub in &cmd.subcommands {
+        check_switches_rec(p, sub, defined);
+    }
+    defined.truncate(len);
+}
+
+fn check_fields_rec(p: &mut Parser, xflags: &ast::XFlags, cmd: &ast::Cmd) {
+    let mut fields = HashSet::new();
+    if cmd.has_subcommands() {
+        fields.insert(xflags.subcommand_field.clone());
+    }
+    let args = cmd.args.iter().map(|it| (it.ident(), it.span));
+    // Repeated switches are reported by `check_switches_rec` already.
+    let mut switches = HashSet::new();
+    let flags = cmd.flags.iter().filter(|it| !it.is_help() && switches.insert(&it.name));
+    let flags = flags.map(|it| (it.ident(), it.span));
+    for (field, span) in args.chain(flags) {
+        if ["self", "super", "crate", "Self", "_"].contains(&field.as_str()) {
+            p.error(span, format!("`{field}` can't be a field name, rename it with `as`"))
+        } else if field == xflags.subcommand_field && cmd.has_subcommands() {
+            p.error(span, format!("field `{field}` holds the subcommand, rename it with `as`"))
+        } else if !fields.insert(field.clone()) {
+            p.error(span, format!("field `{field}` is defined multiple times, rename it with `as`"))
+        }
+    }
+    for sub in &cmd.subcommands {
+        check_fields_rec(p, xflags, sub);
+    }
+}
+
+fn check_types_rec(
+    p: &mut Parser,
+    xflags: &ast::XFlags,
+    cmd: &ast::Cmd,
+    types: &mut HashSet<String>,
+) {
+    let mut names = vec![cmd.ident()];
+    if cmd.has_subcommands() {
+        names.push(cmd.cmd_enum_ident(xflags));
+    }
+    for name in names {
+        if name == "Self" {
+            p.error(
+                cmd.span,
+                format!("`{}` can't be a command name, its type would be `Self`", cmd.name),
+            )
+        } else if !types.insert(name.clone()) {
+            p.error(cmd.span, format!("type `{name}` is defined multiple times"))
+        }
+    }
+    for sub in &cmd.subcommands {
+        check_types_rec(p, xflags, sub, types);
+    }
+}
+
+/// Positionals are filled greedily in order, continuing into the default
+/// subcommand, so some orderings can never be filled as intended.
+fn check_positionals_rec(p: &mut Parser, cmd: &ast::Cmd, is_default: bool) {
+    // The default subcommand is checked as a continuation of its parent.
+    if !is_default {
+        let mut optional = None;
+        let mut repeated = None;
+        for (i, arg) in cmd.args_with_default().into_iter().enumerate() {
+            let name = &arg.val.name;
+            if let Some(prev) = repeated {
+                // Filled from the end, like `DST` in `cp SRC... DST`.
+                if i < cmd.args.len() && arg.arity == ast::Arity::Required {
+                    continue;
+                }
+                let msg = format!(
+                    "`{name}` can never be filled, the repeated argument `{prev}` before it takes all the values"
+                );
+                p.error(arg.span, msg);
+                continue;
+            }
+            match arg.arity {
+                ast::Arity::Optional => optional = optional.or(Some(name)),
+                ast::Arity::Required => {
+                    if let Some(prev) = optional {
+                        let msg = format!(
+                            "required argument `{name}` can't follow optional argument `{prev}`, which takes the value first"
+                        );
+                        p.error(arg.span, msg);
+                    }
+                }
+                ast::Arity::Repeated { .. } => repeated = Some(name),
+            }
+        }
+    }
+    for (i, sub) in cmd.subcommands.iter().enumerate() {
+        check_positionals_rec(p, sub, cmd.default && i == 0);
+    }
+}
+
 macro_rules! format_err {
     ($($tt:tt)*) => {
-        Error { msg: format!($($tt)*) }
+        Error { msg: format!($($tt)*), span: None }
         // panic!($($tt)*)
     };
 }
@@ -75,14 +292,17 @@ fn cmd(p: &mut Parser) -> Result<ast::Cmd> {
 }
 
 fn cmd_impl(p: &mut Parser, anon: bool) -> Result<ast::Cmd> {
+    let mut span = Span::call_site();
     let name = if anon {
         String::new()
     } else {
         p.expect_keyword("cmd")?;
+        span = p.name_span();
         cmd_name(p)?
     };
 
-    let aliases = alias_names(p);
+    // `parse_or_exit!` has no name, so its first switch isn't an alias.
+    let aliases = if anon { Vec::new() } else { alias_names(p) };
 
     let idx = p.idx;
     p.idx += 1;
@@ -95,62 +315,153 @@ fn cmd_impl(p: &mut Parser, anon: bool) -> Result<ast::Cmd> {
         flags: Vec::new(),
         subcommands: Vec::new(),
         default: false,
+        examples: Vec::new(),
+        after_help: None,
+        section: None,
+        derives: None,
+        attrs: Vec::new(),
+        enum_doc: None,
+        enum_attrs: Vec::new(),
         idx,
+        span,
     };
 
     if !anon {
         p.enter_delim(Delimiter::Brace)?;
     }
     while !p.end() {
-        let doc = opt_doc(p)?;
-        let default = !anon && p.eat_keyword("default");
-        if !anon && (default || p.at_keyword("cmd")) {
-            let mut cmd = cmd(p)?;
-            cmd.doc = doc;
-            res.subcommands.push(cmd);
-            if default {
-                if res.default {
-                    bail!("only one subcommand can be default")
-                }
-                res.default = true;
-                res.subcommands.rotate_right(1);
-            }
-        } else {
-            let arity = arity(p)?;
-            let is_val = p.lookahead_punct(':', 1);
-            let name = p.expect_name()?;
-            if name.starts_with('-') {
-                let mut flag = flag(p, name)?;
-                flag.doc = doc;
-                flag.arity = arity;
-                res.flags.push(flag)
-            } else if is_val {
-                p.expect_punct(':')?;
-                let ty = ty(p)?;
-                let val = ast::Val { name, ty };
-                let arg = ast::Arg { arity, doc, val };
-                res.args.push(arg);
-            } else {
-                bail!("expected `--flag` or `arg: Type`")
-            }
-        }
+        cmd_item(p, &mut res, anon, None)?;
     }
     if !anon {
         p.exit_delim()?;
     }
+    if !res.has_subcommands() && (res.enum_doc.is_some() || !res.enum_attrs.is_empty()) {
+        p.error(res.span, "`enum` attributes need subcommands")
+    }
 
-    let mut unique_identifiers = std::collections::HashSet::new();
+    let mut unique_identifiers = HashSet::new();
 
-    for ident in res.subcommands.iter().flat_map(|cmd| cmd.all_identifiers()) {
-        if !unique_identifiers.insert(ident) {
-            bail!("`{}` is defined multiple times", ident)
+    for cmd in &res.subcommands {
+        for ident in cmd.all_identifiers() {
+            if !unique_identifiers.insert(ident) {
+                p.error(cmd.span, format!("`{ident}` is defined multiple times"))
+            }
         }
     }
 
     Ok(res)
 }
 
-fn flag(p: &mut Parser, name: String) -> Result<ast::Flag> {
+/// Parses a single item of a command body: a switch, an argument, a subcommand, etc.
+fn cmd_item(p: &mut Parser, res: &mut ast::Cmd, anon: bool, section: Option<&str>) -> Result<()> {
+    let attrs = attrs(p)?;
+    if !anon && p.at_keyword("enum") {
+        let span = p.span();
+        p.bump();
+        if attrs.derives.is_some() {
+            p.error(span, "`derive` is only allowed on commands")
+        }
+        if section.is_some() {
+            p.error(span, "only switches and subcommands are allowed in a section")
+        }
+        if res.enum_doc.is_some() || !res.enum_attrs.is_empty() {
+            p.error(span, "`enum` is specified multiple times")
+        }
+        res.enum_doc = attrs.doc;
+        res.enum_attrs = attrs.other;
+        return Ok(());
+    }
+    let is_meta = ["section", "example", "after_help"].iter().any(|&kw| p.at_keyword(kw));
+    if !anon && is_meta {
+        let span = p.span();
+        if !attrs.is_empty() {
+            p.error(span, "attributes are not allowed on `section`, `example` and `after_help`")
+        }
+        if section.is_some() {
+            p.error(span, "only switches and subcommands are allowed in a section")
+        }
+    }
+    if !anon && p.eat_keyword("section") {
+        let title = p.expect_string()?;
+        p.enter_delim(Delimiter::Brace)?;
+        while !p.end() {
+            cmd_item(p, res, anon, Some(&title))?;
+        }
+        p.exit_delim()?;
+        return Ok(());
+    }
+    if !anon && p.eat_keyword("example") {
+        let cmdline = p.expect_string()?;
+        let doc = p.eat_string();
+        res.examples.push(ast::Example { cmdline, doc });
+        return Ok(());
+    }
+    if !anon && p.eat_keyword("after_help") {
+        if res.after_help.is_some() {
+            let span = p.span();
+            p.error(span, "`after_help` is specified multiple times")
+        }
+        res.after_help = Some(p.expect_string()?);
+        return Ok(());
+    }
+    let span = p.span();
+    let default = !anon && p.eat_keyword("default");
+    if !anon && (default || p.at_keyword("cmd")) {
+        let mut cmd = cmd(p)?;
+        attrs.apply_to_cmd(&mut cmd);
+        cmd.section = section.map(String::from);
+        res.subcommands.push(cmd);
+        if default {
+            if res.default {
+                p.error(span, "only one subcommand can be default")
+            }
+            res.default = true;
+            res.subcommands.rotate_right(1);
+        }
+    } else {
+        if attrs.derives.is_some() {
+            p.error(span, "`derive` is only allowed on commands")
+        }
+        let arity = arity(p)?;
+        let span = p.span();
+        let name_span = p.name_span();
+        let name = p.expect_name()?;
+        if name.starts_with('-') {
+            let mut flag = flag(p, name, span, name_span)?;
+            flag.doc = attrs.doc;
+            flag.attrs = attrs.other;
+            flag.arity = arity;
+            flag.section = section.map(String::from);
+            if flag.delimiter.is_some() && flag.arity == ast::Arity::Required {
+                let msg = format!(
+                    "delimiter is only allowed for optional and repeated flags: `--{}`",
+                    flag.name
+                );
+                p.error(span, msg)
+            }
+            res.flags.push(flag)
+        } else {
+            if section.is_some() {
+                p.error(span, "only switches and subcommands are allowed in a section")
+            }
+            let (field, span) = match field(p)? {
+                Some((field, span)) => (Some(field), span),
+                None => (None, name_span),
+            };
+            if !p.eat_punct(':') {
+                bail!("expected `--flag` or `arg: Type`")
+            }
+            let ty_span = p.span();
+            let ty = ty(p)?;
+            let val = ast::Val { name, ty, span: ty_span };
+            let arg = ast::Arg { arity, doc: attrs.doc, val, field, attrs: attrs.other, span };
+            res.args.push(arg);
+        }
+    }
+    Ok(())
+}
+
+fn flag(p: &mut Parser, name: String, span: Span, mut name_span: Span) -> Result<ast::Flag> {
     let short;
     let long;
     if name.starts_with("--") {
@@ -159,28 +470,73 @@ fn flag(p: &mut Parser, name: String) -> Result<ast::Flag> {
     } else {
         short = Some(name);
         if !p.eat_punct(',') {
-            bail!("long option is required for `{}`", short.unwrap());
+            return Err(format_err!("long option is required for `{}`", short.unwrap()).at(span));
         }
+        let span = p.span();
+        name_span = p.name_span();
         long = flag_name(p)?;
         if !long.starts_with("--") {
-            bail!("long name must begin with `--`: `{long}`");
+            p.error(span, format!("long name must begin with `--`: `{long}`"));
         }
     }
 
     if long == "--help" {
-        bail!("`--help` flag is generated automatically")
+        p.error(span, "`--help` flag is generated automatically")
     }
 
+    let (field, span) = match field(p)? {
+        Some((field, span)) => (Some(field), span),
+        None => (None, name_span),
+    };
     let val = opt_val(p)?;
+    let delimiter = if p.eat_keyword("delimiter") {
+        if val.is_none() {
+            let span = p.last_span();
+            p.error(span, format!("delimiter requires a value: `{long}`"))
+        }
+        Some(p.expect_char()?)
+    } else {
+        None
+    };
     Ok(ast::Flag {
         arity: ast::Arity::Required,
-        name: long[2..].to_string(),
+        name: long.trim_start_matches('-').to_string(),
         short: short.map(|it| it[1..].to_string()),
         doc: None,
         val,
+        delimiter,
+        field,
+        section: None,
+        attrs: Vec::new(),
+        span,
     })
 }
 
+/// Parses an optional `as field_name` rename.
+fn field(p: &mut Parser) -> Result<Option<(String, Span)>> {
+    if !p.eat_keyword("as") {
+        return Ok(None);
+    }
+    let span = p.span();
+    let field = p.expect_ident()?;
+    Ok(Some((field, span)))
+}
+
+/// Parses the visibility of the top-level command: `pub`, `pub(crate)`, etc.
+fn vis(p: &mut Parser) -> Result<String> {
+    if !p.eat_keyword("pub") {
+        return Ok("pub".to_string());
+    }
+    match p.ts.last() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+            let res = format!("pub{g}");
+            p.bump();
+            Ok(res)
+        }
+        _ => Ok("pub".to_string()),
+    }
+}
+
 fn opt_val(p: &mut Parser) -> Result<Option<ast::Val>, Error> {
     if !p.lookahead_punct(':', 1) {
         return Ok(None);
@@ -188,8 +544,9 @@ fn opt_val(p: &mut Parser) -> Result<Option<ast::Val>, Error> {
 
     let name = p.expect_name()?;
     p.expect_punct(':')?;
+    let span = p.span();
     let ty = ty(p)?;
-    let res = ast::Val { name, ty };
+    let res = ast::Val { name, ty, span };
     Ok(Some(res))
 }
 
@@ -201,12 +558,44 @@ fn arity(p: &mut Parser) -> Result<ast::Arity> {
         return Ok(ast::Arity::Required);
     }
     if p.eat_keyword("repeated") {
-        return Ok(ast::Arity::Repeated);
+        if !p.at_delim(Delimiter::Brace) {
+            return Ok(ast::Arity::Repeated { min: 0, max: None });
+        }
+        return bounds(p);
     }
-    if let Some(name) = p.eat_name() {
-        bail!("expected one of `optional`, `required`, `repeated`, got `{name}`")
+    let next = p.ts.last().map(|it| it.to_string()).unwrap_or_default();
+    bail!("expected one of `optional`, `required`, `repeated`, got `{next}`")
+}
+
+/// Parses occurrence bounds of a repeated item: `{2}`, `{1..}`, `{..=3}`, `{1..4}`.
+fn bounds(p: &mut Parser) -> Result<ast::Arity> {
+    p.enter_delim(Delimiter::Brace)?;
+    let lo = p.eat_int()?;
+    let (min, max) = if p.eat_punct('.') {
+        p.expect_punct('.')?;
+        let inclusive = p.eat_punct('=');
+        let hi = p.eat_int()?;
+        let max = match (hi, inclusive) {
+            (Some(hi), true) => Some(hi),
+            (Some(0), false) => bail!("empty range of occurrences"),
+            (Some(hi), false) => Some(hi - 1),
+            (None, true) => bail!("expected an upper bound after `..=`"),
+            (None, false) => None,
+        };
+        (lo.unwrap_or(0), max)
+    } else {
+        match lo {
+            Some(n) => (n, Some(n)),
+            None => bail!("expected a number or a range of occurrences"),
+        }
+    };
+    p.exit_delim()?;
+    if let Some(max) = max {
+        if max == 0 || min > max {
+            bail!("empty range of occurrences")
+        }
     }
-    bail!("expected one of `optional`, `required`, `repeated`, got {:?}", p.ts.pop())
+    Ok(ast::Arity::Repeated { min, max })
 }
 
 fn ty(p: &mut Parser) -> Result<ast::Ty> {
@@ -219,37 +608,81 @@ fn ty(p: &mut Parser) -> Result<ast::Ty> {
     Ok(res)
 }
 
-fn opt_single_doc(p: &mut Parser) -> Result<Option<String>> {
-    if !p.eat_punct('#') {
-        return Ok(None);
+/// Outer attributes of a command, flag or argument.
+struct Attrs {
+    doc: Option<String>,
+    derives: Option<Vec<String>>,
+    other: Vec<String>,
+}
+
+impl Attrs {
+    fn is_empty(&self) -> bool {
+        self.doc.is_none() && self.derives.is_none() && self.other.is_empty()
     }
-    p.enter_delim(Delimiter::Bracket)?;
-    p.expect_keyword("doc")?;
-    p.expect_punct('=')?;
-    let mut res = p.expect_string()?;
-    if let Some(suf) = res.strip_prefix(' ') {
-        res = suf.to_string();
+
+    fn apply_to_cmd(self, cmd: &mut ast::Cmd) {
+        cmd.doc = self.doc;
+        cmd.derives = self.derives;
+        cmd.attrs = self.other;
     }
-    p.exit_delim()?;
-    Ok(Some(res))
 }
 
-fn opt_doc(p: &mut Parser) -> Result<Option<String>> {
-    let lines =
-        core::iter::from_fn(|| opt_single_doc(p).transpose()).collect::<Result<Vec<String>>>()?;
-    let lines = lines.join("\n");
+fn attrs(p: &mut Parser) -> Result<Attrs> {
+    let mut doc = Vec::new();
+    let mut derives = None;
+    let mut other = Vec::new();
+    while p.eat_punct('#') {
+        let text = match p.ts.last() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => g.to_string(),
+            _ => bail!("expected `[`"),
+        };
+        p.enter_delim(Delimiter::Bracket)?;
+        if p.at_keyword("doc") && p.lookahead_punct('=', 1) {
+            p.expect_keyword("doc")?;
+            p.expect_punct('=')?;
+            let mut line = p.expect_string()?;
+            if let Some(suf) = line.strip_prefix(' ') {
+                line = suf.to_string();
+            }
+            doc.push(line);
+        } else if p.eat_keyword("derive") {
+            if derives.is_some() {
+                bail!("`derive` is specified multiple times")
+            }
+            derives = Some(derive_list(p)?);
+        } else {
+            other.push(format!("#{text}"));
+            while p.bump().is_some() {}
+        }
+        p.exit_delim()?;
+    }
+    let doc = if doc.is_empty() { None } else { Some(doc.join("\n")) };
+    Ok(Attrs { doc, derives, other })
+}
 
-    if lines.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(lines))
+/// Parses `(Debug, Clone)` into a list of paths.
+fn derive_list(p: &mut Parser) -> Result<Vec<String>> {
+    p.enter_delim(Delimiter::Parenthesis)?;
+    let mut res = Vec::new();
+    let mut path = String::new();
+    while let Some(tt) = p.bump() {
+        match tt {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => res.push(mem::take(&mut path)),
+            _ => path.push_str(&tt.to_string()),
+        }
+    }
+    if !path.is_empty() {
+        res.push(path);
     }
+    p.exit_delim()?;
+    Ok(res)
 }
 
 fn cmd_name(p: &mut Parser) -> Result<String> {
+    let span = p.span();
     let name = p.expect_name()?;
     if name.starts_with('-') {
-        bail!("command name can't begin with `-`: `{name}`");
+        p.error(span, format!("command name can't begin with `-`: `{name}`"));
     }
     Ok(name)
 }
@@ -265,9 +698,10 @@ fn alias_names(p: &mut Parser) -> Vec<String> {
 }
 
 fn flag_name(p: &mut Parser) -> Result<String> {
+    let span = p.span();
     let name = p.expect_name()?;
     if !name.starts_with('-') {
-        bail!("flag name should begin with `-`: `{name}`");
+        return Err(format_err!("flag name should begin with `-`: `{name}`").at(span));
     }
     Ok(name)
 }
@@ -276,20 +710,69 @@ struct Parser {
     stack: Vec<Vec<TokenTree>>,
     ts: Vec<TokenTree>,
     idx: u8,
+    /// Span of the last consumed token, for errors at the end of a group.
+    last_span: Option<Span>,
+    /// Errors which don't stop the parsing, so that all of them are reported.
+    errors: Vec<Error>,
 }
 
 impl Parser {
     fn new(ts: TokenStream) -> Self {
         let mut ts = ts.into_iter().collect::<Vec<_>>();
         ts.reverse();
-        Self { stack: Vec::new(), ts, idx: 0 }
+        Self { stack: Vec::new(), ts, idx: 0, last_span: None, errors: Vec::new() }
+    }
+
+    fn finish<T>(&mut self, res: Result<T>) -> Result<T, Vec<Error>> {
+        let mut errors = mem::take(&mut self.errors);
+        match res {
+            Ok(it) if errors.is_empty() => return Ok(it),
+            Ok(_) => (),
+            Err(mut err) => {
+                err.span = Some(err.span.unwrap_or_else(|| self.span()));
+                errors.push(err);
+            }
+        }
+        Err(errors)
+    }
+
+    fn error(&mut self, span: Span, msg: impl Into<String>) {
+        self.errors.push(Error { msg: msg.into(), span: Some(span) })
+    }
+
+    /// Span of the next token.
+    fn span(&self) -> Span {
+        match self.ts.last() {
+            Some(tt) => tt.span(),
+            None => self.last_span(),
+        }
+    }
+    fn last_span(&self) -> Span {
+        self.last_span.unwrap_or_else(Span::call_site)
+    }
+
+    /// Span of the first identifier of the next name, `foo` in `--foo-bar`.
+    fn name_span(&self) -> Span {
+        let mut tokens = self.ts.iter().rev();
+        let ident = tokens.find(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == '-'));
+        match ident {
+            Some(TokenTree::Ident(ident)) => ident.span(),
+            _ => self.span(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<TokenTree> {
+        let tt = self.ts.pop()?;
+        self.last_span = Some(tt.span());
+        Some(tt)
     }
 
     fn enter_delim(&mut self, delimiter: Delimiter) -> Result<()> {
-        match self.ts.pop() {
+        match self.ts.last() {
             Some(TokenTree::Group(g)) if g.delimiter() == delimiter => {
                 let mut ts = g.stream().into_iter().collect::<Vec<_>>();
                 ts.reverse();
+                self.bump();
                 let ts = mem::replace(&mut self.ts, ts);
                 self.stack.push(ts);
             }
@@ -308,6 +791,10 @@ impl Parser {
         self.ts.last().is_none()
     }
 
+    fn at_delim(&mut self, delimiter: Delimiter) -> bool {
+        matches!(self.ts.last(), Some(TokenTree::Group(g)) if g.delimiter() == delimiter)
+    }
+
     fn expect_keyword(&mut self, kw: &str) -> Result<()> {
         if !self.eat_keyword(kw) {
             bail!("expected `{kw}`")
@@ -316,7 +803,7 @@ impl Parser {
     }
     fn eat_keyword(&mut self, kw: &str) -> bool {
         if self.at_keyword(kw) {
-            self.ts.pop().unwrap();
+            self.bump();
             true
         } else {
             false
@@ -332,7 +819,7 @@ impl Parser {
 
     fn expect_name(&mut self) -> Result<String> {
         self.eat_name().ok_or_else(|| {
-            let next = self.ts.pop().map(|it| it.to_string()).unwrap_or_default();
+            let next = self.ts.last().map(|it| it.to_string()).unwrap_or_default();
             format_err!("expected a name, got: `{next}`")
         })
     }
@@ -351,7 +838,7 @@ impl Parser {
                 }
                 _ => break,
             }
-            self.ts.pop();
+            self.bump();
         }
         if buf.is_empty() {
             None
@@ -360,9 +847,13 @@ impl Parser {
         }
     }
 
-    fn _expect_ident(&mut self) -> Result<String> {
-        match self.ts.pop() {
-            Some(TokenTree::Ident(ident)) => Ok(ident.to_string()),
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.ts.last() {
+            Some(TokenTree::Ident(ident)) => {
+                let res = ident.to_string();
+                self.bump();
+                Ok(res)
+            }
             _ => bail!("expected ident"),
         }
     }
@@ -376,7 +867,7 @@ impl Parser {
     fn eat_punct(&mut self, punct: char) -> bool {
         match self.ts.last() {
             Some(TokenTree::Punct(p)) if p.as_char() == punct => {
-                self.ts.pop();
+                self.bump();
                 true
             }
             _ => false,
@@ -390,12 +881,47 @@ impl Parser {
     }
 
     fn expect_string(&mut self) -> Result<String> {
-        match self.ts.pop() {
+        self.eat_string().ok_or_else(|| format_err!("expected a string"))
+    }
+    fn eat_string(&mut self) -> Option<String> {
+        match self.ts.last() {
             Some(TokenTree::Literal(lit)) if lit.to_string().starts_with('"') => {
                 let res = str_lit_value(lit.to_string());
-                Ok(res)
+                self.bump();
+                Some(res)
+            }
+            _ => None,
+        }
+    }
+
+    fn eat_int(&mut self) -> Result<Option<usize>> {
+        let text = match self.ts.last() {
+            Some(TokenTree::Literal(lit)) => lit.to_string(),
+            _ => return Ok(None),
+        };
+        match text.parse::<usize>() {
+            Ok(it) => {
+                self.bump();
+                Ok(Some(it))
             }
-            _ => bail!("expected a string"),
+            Err(_) => bail!("expected a number, got `{text}`"),
+        }
+    }
+
+    fn expect_char(&mut self) -> Result<char> {
+        match self.ts.last() {
+            Some(TokenTree::Literal(lit)) if lit.to_string().starts_with('\'') => {
+                let text = lit.to_string();
+                let mut chars = text[1..text.len() - 1].chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => {
+                        self.bump();
+                        Ok(c)
+                    }
+                    _ => bail!("expected a single character, got {text}"),
+                }
+            }
+            _ => bail!("expected a character"),
         }
     }
 }
@@ -405,5 +931,189 @@ impl Parser {
 /// Really needs support in the proc_macro library:
 /// <https://internals.rust-lang.org/t/getting-value-out-of-proc-macro-literal/14140>
 fn str_lit_value(lit: String) -> String {
-    lit.trim_matches('"').replace("\\'", "'")
+    let text = &lit[1..lit.len() - 1];
+    let mut res = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => res.push('\n'),
+            Some('r') => res.push('\r'),
+            Some('t') => res.push('\t'),
+            Some('0') => res.push('\0'),
+            Some('x') => {
+                let code = chars.by_ref().take(2).collect::<String>();
+                res.extend(u8::from_str_radix(&code, 16).ok().map(char::from));
+            }
+            Some('u') => {
+                let code = chars.by_ref().skip(1).take_while(|&it| it != '}').collect::<String>();
+                res.extend(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32));
+            }
+            // Line continuation, skips the newline and the leading whitespace.
+            Some('\n') => {
+                chars = chars.as_str().trim_start().chars();
+            }
+            Some(c) => res.push(c),
+            None => (),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    fn check_errors(src: &str, expect: Expect) {
+        let errors = super::xflags(src.parse().unwrap()).unwrap_err();
+        let actual = errors
+            .iter()
+            .map(|err| {
+                let start = err.span().start();
+                format!("{}:{}: {err}\n", start.line, start.column)
+            })
+            .collect::<String>();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn reports_all_errors() {
+        check_errors(
+            r#"
+cmd app {
+    default cmd run {}
+    default cmd test {}
+    optional --help
+    #[derive(Debug)]
+    optional --verbose
+    required --jobs n: u32 delimiter ','
+    optional -q, quiet
+}
+"#,
+            expect![[r#"
+                4:4: only one subcommand can be default
+                5:13: `--help` flag is generated automatically
+                7:4: `derive` is only allowed on commands
+                8:13: delimiter is only allowed for optional and repeated flags: `--jobs`
+                9:17: flag name should begin with `-`: `quiet`
+            "#]],
+        );
+    }
+
+    #[test]
+    fn reports_misplaced_enum_attrs() {
+        check_errors(
+            r#"
+cmd app {
+    #[non_exhaustive]
+    enum
+    #[derive(Debug)]
+    enum
+    cmd run {
+        #[non_exhaustive]
+        enum
+    }
+}
+"#,
+            expect![[r#"
+                6:4: `derive` is only allowed on commands
+                6:4: `enum` is specified multiple times
+                7:8: `enum` attributes need subcommands
+            "#]],
+        );
+    }
+
+    #[test]
+    fn options_in_any_order() {
+        check_errors(
+            r#"
+program "a"
+enum_suffix Command
+subcommand_field command
+program "b"
+cmd app {}
+"#,
+            expect![[r#"
+                5:0: `program` is specified multiple times
+            "#]],
+        );
+        let ts = "enum_suffix Kind program \"a\" derive(Clone) cmd app {}".parse().unwrap();
+        let xflags = super::xflags(ts).unwrap();
+        assert_eq!(xflags.enum_suffix, "Kind");
+        assert_eq!(xflags.program.as_deref(), Some("a"));
+        assert_eq!(xflags.derives, ["Clone"]);
+        assert!(!xflags.to_args);
+        let ts = "derive(Clone) to_args cmd app {}".parse().unwrap();
+        assert!(super::xflags(ts).unwrap().to_args);
+    }
+
+    #[test]
+    fn parse_or_exit_program() {
+        let ts = "program \"rm\" optional -r, --recursive".parse().unwrap();
+        let xflags = super::parse_or_exit(ts).unwrap();
+        assert_eq!(xflags.program.as_deref(), Some("rm"));
+        assert!(crate::emit::emit(&xflags).contains("Usage: rm [-r]"));
+    }
+
+    #[test]
+    fn reports_impossible_positionals() {
+        check_errors(
+            r#"
+cmd app {
+    repeated files: PathBuf
+    required out: PathBuf
+    optional mode: u32
+
+    cmd run {
+        optional config: PathBuf
+        required target: String
+    }
+    default cmd check {
+        optional path: PathBuf
+    }
+}
+"#,
+            expect![[r#"
+                5:13: `mode` can never be filled, the repeated argument `files` before it takes all the values
+                12:17: `path` can never be filled, the repeated argument `files` before it takes all the values
+                9:17: required argument `target` can't follow optional argument `config`, which takes the value first
+            "#]],
+        );
+    }
+
+    #[test]
+    fn reports_name_conflicts() {
+        check_errors(
+            r#"
+cmd app {
+    optional -v, --verbose
+    optional -q, --verbose
+    optional -h, --host name: String
+    repeated --file-name
+    optional file_name: String
+    optional --self
+    optional --subcommand
+
+    cmd run {
+        optional -v, --quiet
+    }
+    cmd app-cmd {}
+    cmd self {}
+}
+"#,
+            expect![[r#"
+                4:19: `--verbose` is defined multiple times
+                5:19: `-h` is reserved for help
+                12:23: `-v` is already defined by the parent command `app`
+                6:15: field `file_name` is defined multiple times, rename it with `as`
+                8:15: `self` can't be a field name, rename it with `as`
+                9:15: field `subcommand` holds the subcommand, rename it with `as`
+                14:8: type `AppCmd` is defined multiple times
+                15:8: `self` can't be a command name, its type would be `Self`
+            "#]],
+        );
+    }
 }
@@ -26,6 +26,46 @@ pub(crate) fn in_place(api: &str, path: &Path) {
     fs::write(&path, text.as_bytes()).unwrap();
 }
 
+/// Replaces the text between `<!-- xflags start -->` and `<!-- xflags end -->`
+/// markers in a Markdown file, or appends it to the end of the file.
+pub(crate) fn markdown(reference: &str, path: &Path) {
+    let path = {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        Path::new(&dir).join(path)
+    };
+
+    let text = fs::read_to_string(&path).unwrap_or_else(|_| panic!("failed to read {path:?}"));
+    let text = with_reference(&text, reference)
+        .unwrap_or_else(|err| panic!("failed to update {path:?}: {err}"));
+
+    fs::write(&path, text.as_bytes()).unwrap();
+}
+
+const START: &str = "<!-- xflags start -->";
+const END: &str = "<!-- xflags end -->";
+
+/// Puts `reference` between the markers in `text`. Both markers must be
+/// present, the end one after the start one, or neither.
+pub(crate) fn with_reference(text: &str, reference: &str) -> Result<String, String> {
+    let mut text = text.to_string();
+    // The first end marker has to follow the start marker.
+    let range = match (text.find(START), text.find(END)) {
+        (Some(start_idx), Some(end_idx)) if start_idx < end_idx => {
+            newline_before(&text, start_idx)..newline_after(&text, end_idx)
+        }
+        (Some(_), None) => return Err(format!("`{START}` is not followed by `{END}`")),
+        (_, Some(_)) => return Err(format!("`{END}` has no `{START}` before it")),
+        (None, None) => {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.len()..text.len()
+        }
+    };
+    text.replace_range(range, &format!("{START}\n\n{reference}\n{END}\n"));
+    Ok(text)
+}
+
 pub(crate) fn stdout(api: &str) {
     print!("{}", with_preamble(api))
 }
@@ -95,3 +135,59 @@ fn locate_new(text: &str) -> Option<(Range<usize>, String)> {
     }
     Some((idx..idx, indent))
 }
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    fn check(text: &str, expect: expect_test::Expect) {
+        let actual = match super::with_reference(text, "ref\n") {
+            Ok(it) => it,
+            Err(err) => format!("error: {err}"),
+        };
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn markdown_markers() {
+        check(
+            "# CLI",
+            expect![[r#"
+                # CLI
+                <!-- xflags start -->
+
+                ref
+
+                <!-- xflags end -->
+            "#]],
+        );
+        check(
+            "a\n<!-- xflags start -->\nold\n<!-- xflags end -->\nb\n",
+            expect![[r#"
+                a
+                <!-- xflags start -->
+
+                ref
+
+                <!-- xflags end -->
+                b
+            "#]],
+        );
+        check(
+            "<!-- xflags end -->\n<!-- xflags start -->\n",
+            expect!["error: `<!-- xflags end -->` has no `<!-- xflags start -->` before it"],
+        );
+        check(
+            "<!-- xflags start -->\nold\n",
+            expect!["error: `<!-- xflags start -->` is not followed by `<!-- xflags end -->`"],
+        );
+        check(
+            "old\n<!-- xflags end -->\n",
+            expect!["error: `<!-- xflags end -->` has no `<!-- xflags start -->` before it"],
+        );
+        check(
+            "<!-- xflags end -->\n<!-- xflags start -->\nold\n<!-- xflags end -->\n",
+            expect!["error: `<!-- xflags end -->` has no `<!-- xflags start -->` before it"],
+        );
+    }
+}
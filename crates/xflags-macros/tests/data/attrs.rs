@@ -0,0 +1,21 @@
+xflags! {
+    derive(Debug, Clone, PartialEq, Eq, Default)
+
+    #[non_exhaustive]
+    cmd attrs {
+        /// Name, like "x" or `C:\x`.
+        #[allow(unused)]
+        optional --name name: String
+
+        /// Subcommands of `attrs`.
+        #[allow(clippy::large_enum_variant)]
+        enum
+
+        default cmd run {
+            repeated -v, --verbose
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+        cmd check {}
+    }
+}
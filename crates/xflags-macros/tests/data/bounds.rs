@@ -0,0 +1,9 @@
+xflags! {
+    cmd bounds {
+        /// Files to process.
+        repeated{1..} files: PathBuf
+        /// At most three levels of verbosity.
+        repeated{..=3} -v, --verbose
+        repeated{2} --point coord: i32
+    }
+}
@@ -0,0 +1,16 @@
+xflags! {
+    to_args
+
+    cmd default-args {
+        optional path: PathBuf
+
+        default cmd run {
+            optional --release
+            repeated args: OsString
+        }
+
+        cmd check {
+            repeated --package name: String
+        }
+    }
+}
@@ -0,0 +1,12 @@
+xflags! {
+    to_args
+
+    cmd delimited {
+        /// Features to activate.
+        repeated -F, --features feature: String delimiter ','
+        optional --jobs n: u32 delimiter ':'
+        optional --paths path: PathBuf delimiter ','
+        /// Bounds count occurrences of the switch, not values.
+        repeated{..=1} --targets target: String delimiter ','
+    }
+}
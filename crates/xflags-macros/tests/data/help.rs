@@ -1,4 +1,6 @@
 xflags! {
+    markdown "tests/it/help.md"
+
     /// Does stuff
     ///
     /// Helpful stuff.
@@ -16,6 +18,18 @@ xflags! {
         /// And a switch.
         required -s, --switch
 
+        section "Advanced options" {
+            /// Number of threads.
+            optional -j, --jobs n: u32
+
+            /// Remove build artifacts.
+            cmd clean {}
+        }
+
+        example "helpful -s src" "Run on the `src` directory."
+        example "helpful -s"
+        after_help "See also: \"helpful sub --help\"."
+
         /// And even a subcommand!
         cmd sub {
             /// With an optional flag. This has a really long
@@ -0,0 +1,19 @@
+xflags! {
+    to_args
+    subcommand_field command
+    enum_suffix Command
+    program "nm"
+
+    pub(crate) cmd naming {
+        optional -t, --type as kind ty: String
+        required path as target: PathBuf
+        optional --ref name: String
+
+        cmd build {
+            repeated --crate as crates name: String
+        }
+        cmd test {
+            optional --match pattern: String
+        }
+    }
+}
@@ -1,4 +1,6 @@
 xflags! {
+    to_args
+
     cmd RepeatedPos {
         required a: PathBuf
         optional b: u32
@@ -1,4 +1,6 @@
 xflags! {
+    to_args
+
     /// LSP server for rust.
     cmd rust-analyzer {
         required workspace: PathBuf
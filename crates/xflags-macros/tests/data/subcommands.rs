@@ -1,4 +1,6 @@
 xflags! {
+    to_args
+
     cmd rust-analyzer {
         repeated -v, --verbose
 
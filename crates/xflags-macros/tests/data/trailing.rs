@@ -0,0 +1,11 @@
+xflags! {
+    to_args
+
+    cmd trailing {
+        optional -r, --recursive
+        required first: String
+        repeated{1..} sources: PathBuf
+        required dest: PathBuf
+        required mode: u32
+    }
+}
@@ -2,6 +2,7 @@
 #[allow(unused)]
 use std::{ffi::OsString, path::PathBuf};
 
+/// commands with different aliases
 #[derive(Debug)]
 pub struct AliasCmd {
     pub subcommand: AliasCmdCmd,
@@ -9,12 +10,15 @@ pub struct AliasCmd {
 
 #[derive(Debug)]
 pub enum AliasCmdCmd {
+    /// And even an aliased subcommand!
     Sub(Sub),
     This(This),
 }
 
+/// And even an aliased subcommand!
 #[derive(Debug)]
 pub struct Sub {
+    /// Little sanity check to see if this still works as intended
     pub count: Option<usize>,
 }
 
@@ -36,6 +40,41 @@ impl AliasCmd {
     pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
         Self::from_vec_(args)
     }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
 }
 
 impl AliasCmd {
@@ -50,49 +89,102 @@ impl AliasCmd {
         let mut p = xflags::rt::Parser::new(args);
         Self::parse_(&mut p)
     }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
 }
 
 impl AliasCmd {
     fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
         #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("alias-cmd");
         let mut sub__count = Vec::new();
 
         let mut state_ = 0u8;
-        while let Some(arg_) = p_.pop_flag() {
-            match arg_ {
-                Ok(flag_) => match (state_, flag_.as_str()) {
-                    (0, "--help" | "-h") => return Err(p_.help(Self::HELP_)),
-                    (1, "--help" | "-h") => return Err(p_.help(Self::HELP_SUB__)),
-                    (1, "--count" | "-c") => {
-                        sub__count.push(p_.next_value_from_str::<usize>(&flag_)?)
-                    }
-                    (2, "--help" | "-h") => return Err(p_.help(Self::HELP_THIS__)),
-                    _ => return Err(p_.unexpected_flag(&flag_)),
-                },
-                Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
-                    (0, "sub" | "s") => state_ = 1,
-                    (0, "this" | "one" | "has" | "a" | "lot" | "of" | "aliases") => state_ = 2,
-                    (0, "help") => return Err(p_.help(Self::HELP_)),
-                    (0, _) => {
-                        return Err(p_.unexpected_arg(arg_));
-                    }
-                    (1, "help") => return Err(p_.help(Self::HELP_SUB__)),
-                    (2, "help") => return Err(p_.help(Self::HELP_THIS__)),
-                    _ => return Err(p_.unexpected_arg(arg_)),
-                },
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (1, "--help") => return Err(p_.help(Self::HELP_SUB__)),
+                        (1, "-h") => return Err(p_.help(Self::HELP_SHORT_SUB__)),
+                        (1, "--count" | "-c") => {
+                            sub__count.push(p_.next_value_from_str::<usize>(&flag_, "usize")?)
+                        }
+                        (2, "--help") => return Err(p_.help(Self::HELP_THIS__)),
+                        (2, "-h") => return Err(p_.help(Self::HELP_SHORT_THIS__)),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, "sub" | "s") => {
+                            state_ = 1;
+                            p_.enter_subcommand();
+                        }
+                        (0, "this" | "one" | "has" | "a" | "lot" | "of" | "aliases") => {
+                            state_ = 2;
+                            p_.enter_subcommand();
+                        }
+                        (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (0, _) => {
+                            p_.unexpected_arg(arg_)?;
+                        }
+                        (1, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (2, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
             }
-        }
-        Ok(AliasCmd {
-            subcommand: match state_ {
-                1 => AliasCmdCmd::Sub(Sub { count: p_.optional("--count", sub__count)? }),
-                2 => AliasCmdCmd::This(This {}),
-                _ => return Err(p_.subcommand_required()),
-            },
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    1 => Self::HELP_SUB__,
+                    2 => Self::HELP_THIS__,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            if state_ == 0 {
+                p_.subcommand_required();
+            }
+            if matches!(state_, 1) {
+                p_.check_count("--count <count>", sub__count.len(), 0, Some(1));
+            }
+            p_.finish_checks()?;
+            Ok(AliasCmd {
+                subcommand: match state_ {
+                    1 => AliasCmdCmd::Sub(Sub { count: sub__count.pop() }),
+                    2 => AliasCmdCmd::This(This {}),
+                    _ => unreachable!(),
+                },
+            })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("alias-cmd", "alias-cmd [-h] <COMMAND>", true),
+                1 => ("alias-cmd sub", "alias-cmd sub [-c <count>]", true),
+                2 => ("alias-cmd this", "alias-cmd this", true),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
         })
     }
 }
 impl AliasCmd {
-    const HELP_SUB__: &'static str = "Usage: sub [-c <count>]
+    const HELP_SUB__: &'static str = "Usage: alias-cmd sub [-c <count>]
 
 And even an aliased subcommand!
 
@@ -101,7 +193,19 @@ Options:
 
 Commands:
   help                 Print this message or the help of the given subcommand(s)";
-    const HELP_THIS__: &'static str = "Usage: this
+    const HELP_SHORT_SUB__: &'static str = "Usage: alias-cmd sub [-c <count>]
+
+And even an aliased subcommand!
+
+Options:
+  -c, --count <count>  Little sanity check to see if this still works as intended
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_THIS__: &'static str = "Usage: alias-cmd this
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_THIS__: &'static str = "Usage: alias-cmd this
 Commands:
   help                 Print this message or the help of the given subcommand(s)";
     const HELP_: &'static str = "Usage: alias-cmd [-h] <COMMAND>
@@ -115,4 +219,88 @@ Commands:
   sub                  And even an aliased subcommand!
   this                 
   help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_: &'static str = "Usage: alias-cmd [-h] <COMMAND>
+
+commands with different aliases
+
+Options:
+  -h, --help           Prints help
+
+Commands:
+  sub                  And even an aliased subcommand!
+  this                 
+  help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"alias-cmd\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": \"commands with different aliases\",
+  \"section\": null,
+  \"args\": [],
+  \"flags\": [
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [
+    {
+      \"name\": \"sub\",
+      \"aliases\": [
+        \"s\"
+      ],
+      \"default\": false,
+      \"doc\": \"And even an aliased subcommand!\",
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"count\",
+          \"short\": \"c\",
+          \"arity\": \"optional\",
+          \"min\": 0,
+          \"max\": 1,
+          \"value\": {
+            \"name\": \"count\",
+            \"type\": \"usize\"
+          },
+          \"delimiter\": null,
+          \"doc\": \"Little sanity check to see if this still works as intended\",
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    },
+    {
+      \"name\": \"this\",
+      \"aliases\": [
+        \"one\",
+        \"has\",
+        \"a\",
+        \"lot\",
+        \"of\",
+        \"aliases\"
+      ],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    }
+  ],
+  \"examples\": [],
+  \"after_help\": null
+}";
 }
@@ -0,0 +1,306 @@
+#![allow(dead_code)] // unused fields
+#[allow(unused)]
+use std::{ffi::OsString, path::PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Attrs {
+    /// Name, like "x" or `C:\x`.
+    #[allow(unused)]
+    pub name: Option<String>,
+    pub subcommand: AttrsCmd,
+}
+
+/// Subcommands of `attrs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum AttrsCmd {
+    Run(Run),
+    Check(Check),
+}
+
+impl Default for AttrsCmd {
+    fn default() -> Self {
+        Self::Run(Run::default())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Run {
+    pub verbose: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+pub struct Check;
+
+impl Attrs {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+}
+
+impl Attrs {
+    fn from_env_or_exit_() -> Self {
+        Self::from_env_().unwrap_or_else(|err| err.exit())
+    }
+    fn from_env_() -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_env();
+        Self::parse_(&mut p)
+    }
+    fn from_vec_(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new(args);
+        Self::parse_(&mut p)
+    }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+}
+
+impl Attrs {
+    fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
+        #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("attrs");
+        let mut name = Vec::new();
+        let mut run__verbose = Vec::new();
+
+        let mut state_ = 0u8;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0..=2, "--name") => {
+                            name.push(p_.next_value_from_str::<String>(&flag_, "String")?)
+                        }
+                        (0, _) => {
+                            p_.push_back(Ok(flag_));
+                            state_ = 1;
+                        }
+                        (1, "--help") => return Err(p_.help(Self::HELP_RUN__)),
+                        (1, "-h") => return Err(p_.help(Self::HELP_SHORT_RUN__)),
+                        (1, "--verbose" | "-v") => run__verbose.push(()),
+                        (2, "--help") => return Err(p_.help(Self::HELP_CHECK__)),
+                        (2, "-h") => return Err(p_.help(Self::HELP_SHORT_CHECK__)),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, "check") => {
+                            state_ = 2;
+                            p_.enter_subcommand();
+                        }
+                        (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (0, _) => {
+                            p_.push_back(Err(arg_));
+                            state_ = 1;
+                        }
+                        (1, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (2, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    1 => Self::HELP_RUN__,
+                    2 => Self::HELP_CHECK__,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            state_ = if state_ == 0 { 1 } else { state_ };
+            p_.check_count("--name <name>", name.len(), 0, Some(1));
+            p_.finish_checks()?;
+            Ok(Attrs {
+                name: name.pop(),
+                subcommand: match state_ {
+                    1 => AttrsCmd::Run(Run { verbose: run__verbose.len() as u32 }),
+                    2 => AttrsCmd::Check(Check {}),
+                    _ => unreachable!(),
+                },
+            })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("attrs", "attrs [--name <name>] [-h] [-v]... <COMMAND>", true),
+                1 => ("attrs run", "attrs run [-v]...", true),
+                2 => ("attrs check", "attrs check", true),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
+    }
+}
+impl Attrs {
+    const HELP_RUN__: &'static str = "Usage: attrs run [-v]...
+Options:
+  -v, --verbose        
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_RUN__: &'static str = "Usage: attrs run [-v]...
+Options:
+  -v, --verbose        
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_CHECK__: &'static str = "Usage: attrs check
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_CHECK__: &'static str = "Usage: attrs check
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_: &'static str = "Usage: attrs [--name <name>] [-h] [-v]... <COMMAND>
+Options:
+  --name <name>        Name, like \"x\" or `C:\\x`.
+  -h, --help           Prints help
+  -v, --verbose        
+
+Commands:
+  check                
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_: &'static str = "Usage: attrs [--name <name>] [-h] [-v]... <COMMAND>
+Options:
+  --name <name>        Name, like \"x\" or `C:\\x`.
+  -h, --help           Prints help
+  -v, --verbose        
+
+Commands:
+  check                
+  help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"attrs\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [],
+  \"flags\": [
+    {
+      \"long\": \"name\",
+      \"short\": null,
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"name\",
+        \"type\": \"String\"
+      },
+      \"delimiter\": null,
+      \"doc\": \"Name, like \\\"x\\\" or `C:\\\\x`.\",
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [
+    {
+      \"name\": \"run\",
+      \"aliases\": [],
+      \"default\": true,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"verbose\",
+          \"short\": \"v\",
+          \"arity\": \"repeated\",
+          \"min\": 0,
+          \"max\": null,
+          \"value\": null,
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    },
+    {
+      \"name\": \"check\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    }
+  ],
+  \"examples\": [],
+  \"after_help\": null
+}";
+}
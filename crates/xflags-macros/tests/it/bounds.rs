@@ -0,0 +1,225 @@
+#![allow(dead_code)] // unused fields
+#[allow(unused)]
+use std::{ffi::OsString, path::PathBuf};
+
+#[derive(Debug)]
+pub struct Bounds {
+    /// Files to process.
+    pub files: Vec<PathBuf>,
+
+    /// At most three levels of verbosity.
+    pub verbose: u32,
+    pub point: Vec<i32>,
+}
+
+impl Bounds {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+}
+
+impl Bounds {
+    fn from_env_or_exit_() -> Self {
+        Self::from_env_().unwrap_or_else(|err| err.exit())
+    }
+    fn from_env_() -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_env();
+        Self::parse_(&mut p)
+    }
+    fn from_vec_(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new(args);
+        Self::parse_(&mut p)
+    }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+}
+
+impl Bounds {
+    fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
+        #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("bounds");
+        let mut verbose = Vec::new();
+        let mut point = Vec::new();
+        let mut files = (false, Vec::new());
+
+        let mut state_ = 0u8;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0, "--verbose" | "-v") => verbose.push(()),
+                        (0, "--point") => point.push(p_.next_value_from_str::<i32>(&flag_, "i32")?),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, _) => {
+                            if let (false, buf_) = &mut files {
+                                buf_.push(arg_.into());
+                                continue;
+                            }
+                            p_.unexpected_arg(arg_)?;
+                        }
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            p_.check_count("--verbose", verbose.len(), 0, Some(3));
+            p_.check_count("--point <coord>", point.len(), 2, Some(2));
+            p_.check_count("<files>...", files.1.len(), 1, None);
+            p_.finish_checks()?;
+            Ok(Bounds { verbose: verbose.len() as u32, point, files: files.1 })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("bounds", "bounds <files>... [-v]... --point <coord>... [-h]", false),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
+    }
+}
+impl Bounds {
+    const HELP_: &'static str = "Usage: bounds <files>... [-v]... --point <coord>... [-h]
+Arguments:
+  <files>...           Files to process.
+
+Options:
+  -v, --verbose        At most three levels of verbosity.
+  --point <coord>      
+  -h, --help           Prints help";
+    const HELP_SHORT_: &'static str = "Usage: bounds <files>... [-v]... --point <coord>... [-h]
+Arguments:
+  <files>...           Files to process.
+
+Options:
+  -v, --verbose        At most three levels of verbosity.
+  --point <coord>      
+  -h, --help           Prints help";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"bounds\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"files\",
+      \"arity\": \"repeated\",
+      \"min\": 1,
+      \"max\": null,
+      \"type\": \"PathBuf\",
+      \"doc\": \"Files to process.\"
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"verbose\",
+      \"short\": \"v\",
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": 3,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"At most three levels of verbosity.\",
+      \"section\": null
+    },
+    {
+      \"long\": \"point\",
+      \"short\": null,
+      \"arity\": \"repeated\",
+      \"min\": 2,
+      \"max\": 2,
+      \"value\": {
+        \"name\": \"coord\",
+        \"type\": \"i32\"
+      },
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [],
+  \"examples\": [],
+  \"after_help\": null
+}";
+}
@@ -0,0 +1,403 @@
+#![allow(dead_code)] // unused fields
+#[allow(unused)]
+use std::{ffi::OsString, path::PathBuf};
+
+#[derive(Debug)]
+pub struct DefaultArgs {
+    pub path: Option<PathBuf>,
+    pub subcommand: DefaultArgsCmd,
+}
+
+#[derive(Debug)]
+pub enum DefaultArgsCmd {
+    Run(Run),
+    Check(Check),
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub args: Vec<OsString>,
+
+    pub release: bool,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub package: Vec<String>,
+}
+
+impl DefaultArgs {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
+}
+
+impl DefaultArgs {
+    fn from_env_or_exit_() -> Self {
+        Self::from_env_().unwrap_or_else(|err| err.exit())
+    }
+    fn from_env_() -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_env();
+        Self::parse_(&mut p)
+    }
+    fn from_vec_(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new(args);
+        Self::parse_(&mut p)
+    }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
+}
+
+impl DefaultArgs {
+    fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
+        #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("default-args");
+        let mut path = (false, Vec::new());
+        let mut run__release = Vec::new();
+        let mut run__args = (false, Vec::new());
+        let mut check__package = Vec::new();
+
+        let mut state_ = 0u8;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0, _) => {
+                            p_.push_back(Ok(flag_));
+                            state_ = 1;
+                        }
+                        (1, "--help") => return Err(p_.help(Self::HELP_RUN__)),
+                        (1, "-h") => return Err(p_.help(Self::HELP_SHORT_RUN__)),
+                        (1, "--release") => run__release.push(()),
+                        (2, "--help") => return Err(p_.help(Self::HELP_CHECK__)),
+                        (2, "-h") => return Err(p_.help(Self::HELP_SHORT_CHECK__)),
+                        (2, "--package") => {
+                            check__package.push(p_.next_value_from_str::<String>(&flag_, "String")?)
+                        }
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, "check") => {
+                            state_ = 2;
+                            p_.enter_subcommand();
+                        }
+                        (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (0, _) => {
+                            if let (done_ @ false, buf_) = &mut path {
+                                buf_.push(arg_.into());
+                                *done_ = true;
+                                continue;
+                            }
+                            p_.push_back(Err(arg_));
+                            state_ = 1;
+                        }
+                        (1, _) => {
+                            if let (done_ @ false, buf_) = &mut path {
+                                buf_.push(arg_.into());
+                                *done_ = true;
+                                continue;
+                            }
+                            if let (false, buf_) = &mut run__args {
+                                buf_.push(arg_);
+                                continue;
+                            }
+                            p_.unexpected_arg(arg_)?;
+                        }
+                        (2, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    1 => Self::HELP_RUN__,
+                    2 => Self::HELP_CHECK__,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            state_ = if state_ == 0 { 1 } else { state_ };
+            p_.check_count("<path>", path.1.len(), 0, Some(1));
+            if matches!(state_, 1) {
+                p_.check_count("--release", run__release.len(), 0, Some(1));
+            }
+            p_.finish_checks()?;
+            Ok(DefaultArgs {
+                path: path.1.pop(),
+                subcommand: match state_ {
+                    1 => DefaultArgsCmd::Run(Run {
+                        release: !run__release.is_empty(),
+                        args: run__args.1,
+                    }),
+                    2 => DefaultArgsCmd::Check(Check { package: check__package }),
+                    _ => unreachable!(),
+                },
+            })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => (
+                    "default-args",
+                    "default-args [path] [args]... [-h] [--release] <COMMAND>",
+                    true,
+                ),
+                1 => ("default-args run", "default-args run [args]... [--release]", false),
+                2 => ("default-args check", "default-args check [--package <name>]...", true),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
+    }
+}
+impl DefaultArgs {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if let Some(it) = &self.path {
+            w_.arg(it, true);
+        }
+        match &self.subcommand {
+            DefaultArgsCmd::Run(it) => {
+                it.unparse_(w_);
+            }
+            DefaultArgsCmd::Check(it) => {
+                w_.subcommand("check");
+                it.unparse_(w_);
+            }
+        }
+    }
+}
+impl Run {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if self.release {
+            w_.flag("--release");
+        }
+        for it in &self.args {
+            w_.arg(it, false);
+        }
+    }
+}
+impl Check {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        for it in &self.package {
+            w_.flag("--package");
+            w_.value(it);
+        }
+    }
+}
+impl DefaultArgs {
+    const HELP_RUN__: &'static str = "Usage: default-args run [args]... [--release]
+Arguments:
+  [args]...            
+
+Options:
+  --release            ";
+    const HELP_SHORT_RUN__: &'static str = "Usage: default-args run [args]... [--release]
+Arguments:
+  [args]...            
+
+Options:
+  --release            ";
+    const HELP_CHECK__: &'static str = "Usage: default-args check [--package <name>]...
+Options:
+  --package <name>     
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_CHECK__: &'static str = "Usage: default-args check [--package <name>]...
+Options:
+  --package <name>     
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_: &'static str = "Usage: default-args [path] [args]... [-h] [--release] <COMMAND>
+Arguments:
+  [path]               
+  [args]...            
+
+Options:
+  -h, --help           Prints help
+  --release            
+
+Commands:
+  check                
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_: &'static str =
+        "Usage: default-args [path] [args]... [-h] [--release] <COMMAND>
+Arguments:
+  [path]               
+  [args]...            
+
+Options:
+  -h, --help           Prints help
+  --release            
+
+Commands:
+  check                
+  help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"default-args\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"path\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"type\": \"PathBuf\",
+      \"doc\": null
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [
+    {
+      \"name\": \"run\",
+      \"aliases\": [],
+      \"default\": true,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [
+        {
+          \"name\": \"args\",
+          \"arity\": \"repeated\",
+          \"min\": 0,
+          \"max\": null,
+          \"type\": \"OsString\",
+          \"doc\": null
+        }
+      ],
+      \"flags\": [
+        {
+          \"long\": \"release\",
+          \"short\": null,
+          \"arity\": \"optional\",
+          \"min\": 0,
+          \"max\": 1,
+          \"value\": null,
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    },
+    {
+      \"name\": \"check\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"package\",
+          \"short\": null,
+          \"arity\": \"repeated\",
+          \"min\": 0,
+          \"max\": null,
+          \"value\": {
+            \"name\": \"name\",
+            \"type\": \"String\"
+          },
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    }
+  ],
+  \"examples\": [],
+  \"after_help\": null
+}";
+}
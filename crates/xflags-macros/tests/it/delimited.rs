@@ -0,0 +1,288 @@
+#![allow(dead_code)] // unused fields
+#[allow(unused)]
+use std::{ffi::OsString, path::PathBuf};
+
+#[derive(Debug)]
+pub struct Delimited {
+    /// Features to activate.
+    pub features: Vec<String>,
+    pub jobs: Vec<u32>,
+    pub paths: Vec<PathBuf>,
+    /// Bounds count occurrences of the switch, not values.
+    pub targets: Vec<String>,
+}
+
+impl Delimited {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
+}
+
+impl Delimited {
+    fn from_env_or_exit_() -> Self {
+        Self::from_env_().unwrap_or_else(|err| err.exit())
+    }
+    fn from_env_() -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_env();
+        Self::parse_(&mut p)
+    }
+    fn from_vec_(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new(args);
+        Self::parse_(&mut p)
+    }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
+}
+
+impl Delimited {
+    fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
+        #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("delimited");
+        let mut features = Vec::new();
+        let mut jobs = Vec::new();
+        let mut paths = Vec::new();
+        let mut targets = Vec::new();
+
+        let mut state_ = 0u8;
+        let mut help_ = false;
+        let mut parse_ =
+            || -> xflags::Result<Self> {
+                while let Some(arg_) = p_.pop_flag() {
+                    match arg_ {
+                        Ok(flag_) => match (state_, flag_.as_str()) {
+                            (0, "--help") => return Err(p_.help(Self::HELP_)),
+                            (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                            (0, "--features" | "-F") => features
+                                .push(p_.next_values_from_str::<String>(&flag_, "String", ',')?),
+                            (0, "--jobs") => {
+                                jobs.push(p_.next_values_from_str::<u32>(&flag_, "u32", ':')?)
+                            }
+                            (0, "--paths") => paths.push(p_.next_values::<PathBuf>(&flag_, ',')?),
+                            (0, "--targets") => targets
+                                .push(p_.next_values_from_str::<String>(&flag_, "String", ',')?),
+                            _ => p_.unexpected_flag(flag_)?,
+                        },
+                        Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                            (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                            _ => p_.unexpected_arg(arg_)?,
+                        },
+                    }
+                }
+                if help_ {
+                    let help_ = match state_ {
+                        0 => Self::HELP_,
+                        _ => unreachable!(),
+                    };
+                    return Err(p_.help(help_));
+                }
+                p_.check_count("--jobs <n>:...", jobs.len(), 0, Some(1));
+                p_.check_count("--paths <path>,...", paths.len(), 0, Some(1));
+                p_.check_count("--targets <target>,...", targets.len(), 0, Some(1));
+                p_.finish_checks()?;
+                Ok(Delimited {
+                    features: features.into_iter().flatten().collect(),
+                    jobs: jobs.pop().unwrap_or_default(),
+                    paths: paths.pop().unwrap_or_default(),
+                    targets: targets.into_iter().flatten().collect(),
+                })
+            };
+        parse_().map_err(|err_| {
+let (command_, usage_, help_command_) = match state_ {
+0 => ("delimited", "delimited [-F <feature>,...]... [--jobs <n>:...] [--paths <path>,...] [--targets <target>,...]... [-h]", true),
+_ => unreachable!(),
+};
+p_.with_usage(err_, command_, usage_, help_command_)
+})
+    }
+}
+impl Delimited {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if !self.features.is_empty() {
+            w_.flag("--features");
+            w_.values(self.features.iter(), ',');
+        }
+        if !self.jobs.is_empty() {
+            w_.flag("--jobs");
+            w_.values(self.jobs.iter().map(|it| it.to_string()), ':');
+        }
+        if !self.paths.is_empty() {
+            w_.flag("--paths");
+            w_.values(self.paths.iter(), ',');
+        }
+        if !self.targets.is_empty() {
+            w_.flag("--targets");
+            w_.values(self.targets.iter(), ',');
+        }
+    }
+}
+impl Delimited {
+    const HELP_: &'static str = "Usage: delimited [-F <feature>,...]... [--jobs <n>:...] [--paths <path>,...] [--targets <target>,...]... [-h]
+Options:
+  -F, --features <feature>,... Features to activate.
+  --jobs <n>:...       
+  --paths <path>,...   
+  --targets <target>,... Bounds count occurrences of the switch, not values.
+  -h, --help           Prints help
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_: &'static str = "Usage: delimited [-F <feature>,...]... [--jobs <n>:...] [--paths <path>,...] [--targets <target>,...]... [-h]
+Options:
+  -F, --features <feature>,... Features to activate.
+  --jobs <n>:...       
+  --paths <path>,...   
+  --targets <target>,... Bounds count occurrences of the switch, not values.
+  -h, --help           Prints help
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"delimited\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [],
+  \"flags\": [
+    {
+      \"long\": \"features\",
+      \"short\": \"F\",
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": null,
+      \"value\": {
+        \"name\": \"feature\",
+        \"type\": \"String\"
+      },
+      \"delimiter\": \",\",
+      \"doc\": \"Features to activate.\",
+      \"section\": null
+    },
+    {
+      \"long\": \"jobs\",
+      \"short\": null,
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"n\",
+        \"type\": \"u32\"
+      },
+      \"delimiter\": \":\",
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"paths\",
+      \"short\": null,
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"path\",
+        \"type\": \"PathBuf\"
+      },
+      \"delimiter\": \",\",
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"targets\",
+      \"short\": null,
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"target\",
+        \"type\": \"String\"
+      },
+      \"delimiter\": \",\",
+      \"doc\": \"Bounds count occurrences of the switch, not values.\",
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [],
+  \"examples\": [],
+  \"after_help\": null
+}";
+}
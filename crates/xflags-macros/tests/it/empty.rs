@@ -20,6 +20,41 @@ impl Empty {
     pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
         Self::from_vec_(args)
     }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
 }
 
 impl Empty {
@@ -34,26 +69,61 @@ impl Empty {
         let mut p = xflags::rt::Parser::new(args);
         Self::parse_(&mut p)
     }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
 }
 
 impl Empty {
     fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
         #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("empty");
 
         let mut state_ = 0u8;
-        if let Some(arg_) = p_.pop_flag() {
-            match arg_ {
-                Ok(flag_) => match (state_, flag_.as_str()) {
-                    (0, "--help" | "-h") => return Err(p_.help(Self::HELP_)),
-                    _ => return Err(p_.unexpected_flag(&flag_)),
-                },
-                Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
-                    (0, "help") => return Err(p_.help(Self::HELP_)),
-                    _ => return Err(p_.unexpected_arg(arg_)),
-                },
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
             }
-        }
-        Ok(Empty {})
+            p_.finish_checks()?;
+            Ok(Empty {})
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("empty", "empty [-h]", true),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
     }
 }
 impl Empty {
@@ -63,4 +133,34 @@ Options:
 
 Commands:
   help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_: &'static str = "Usage: empty [-h]
+Options:
+  -h, --help           Prints help
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"empty\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [],
+  \"flags\": [
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [],
+  \"examples\": [],
+  \"after_help\": null
+}";
 }
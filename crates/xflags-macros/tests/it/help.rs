@@ -2,22 +2,44 @@
 #[allow(unused)]
 use std::{ffi::OsString, path::PathBuf};
 
+/// Does stuff
+///
+/// Helpful stuff.
 #[derive(Debug)]
 pub struct Helpful {
+    /// With an arg.
     pub src: Option<PathBuf>,
+    /// Another arg.
+    ///
+    /// This time, we provide some extra info about the
+    /// arg. Maybe some caveats, or what kinds of
+    /// values are accepted.
     pub extra: Option<String>,
 
+    /// And a switch.
     pub switch: (),
+    /// Number of threads.
+    pub jobs: Option<u32>,
     pub subcommand: HelpfulCmd,
 }
 
 #[derive(Debug)]
 pub enum HelpfulCmd {
+    /// Remove build artifacts.
+    Clean(Clean),
+    /// And even a subcommand!
     Sub(Sub),
 }
 
+/// Remove build artifacts.
+#[derive(Debug)]
+pub struct Clean;
+
+/// And even a subcommand!
 #[derive(Debug)]
 pub struct Sub {
+    /// With an optional flag. This has a really long
+    /// description which spans multiple lines.
     pub flag: bool,
 }
 
@@ -36,6 +58,41 @@ impl Helpful {
     pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
         Self::from_vec_(args)
     }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
 }
 
 impl Helpful {
@@ -50,69 +107,157 @@ impl Helpful {
         let mut p = xflags::rt::Parser::new(args);
         Self::parse_(&mut p)
     }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
 }
 
 impl Helpful {
     fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
         #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("helpful");
         let mut switch = Vec::new();
+        let mut jobs = Vec::new();
         let mut src = (false, Vec::new());
         let mut extra = (false, Vec::new());
         let mut sub__flag = Vec::new();
 
         let mut state_ = 0u8;
-        while let Some(arg_) = p_.pop_flag() {
-            match arg_ {
-                Ok(flag_) => match (state_, flag_.as_str()) {
-                    (0, "--help" | "-h") => return Err(p_.help(Self::HELP_)),
-                    (0 | 1, "--switch" | "-s") => switch.push(()),
-                    (1, "--help" | "-h") => return Err(p_.help(Self::HELP_SUB__)),
-                    (1, "--flag" | "-f") => sub__flag.push(()),
-                    _ => return Err(p_.unexpected_flag(&flag_)),
-                },
-                Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
-                    (0, "sub") => state_ = 1,
-                    (0, _) => {
-                        if let (done_ @ false, buf_) = &mut src {
-                            buf_.push(arg_.into());
-                            *done_ = true;
-                            continue;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0..=2, "--switch" | "-s") => switch.push(()),
+                        (0..=2, "--jobs" | "-j") => {
+                            jobs.push(p_.next_value_from_str::<u32>(&flag_, "u32")?)
                         }
-                        if let (done_ @ false, buf_) = &mut extra {
-                            buf_.push(p_.value_from_str::<String>("extra", arg_)?);
-                            *done_ = true;
-                            continue;
+                        (1, "--help") => return Err(p_.help(Self::HELP_CLEAN__)),
+                        (1, "-h") => return Err(p_.help(Self::HELP_SHORT_CLEAN__)),
+                        (2, "--help") => return Err(p_.help(Self::HELP_SUB__)),
+                        (2, "-h") => return Err(p_.help(Self::HELP_SHORT_SUB__)),
+                        (2, "--flag" | "-f") => sub__flag.push(()),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, "clean") => {
+                            state_ = 1;
+                            p_.enter_subcommand();
                         }
-                        return Err(p_.unexpected_arg(arg_));
-                    }
-                    (1, "help") => return Err(p_.help(Self::HELP_SUB__)),
-                    _ => return Err(p_.unexpected_arg(arg_)),
-                },
+                        (0, "sub") => {
+                            state_ = 2;
+                            p_.enter_subcommand();
+                        }
+                        (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (0, _) => {
+                            if let (done_ @ false, buf_) = &mut src {
+                                buf_.push(arg_.into());
+                                *done_ = true;
+                                continue;
+                            }
+                            if let (done_ @ false, buf_) = &mut extra {
+                                buf_.push(p_.value_from_str::<String>("extra", "String", arg_)?);
+                                *done_ = true;
+                                continue;
+                            }
+                            p_.unexpected_arg(arg_)?;
+                        }
+                        (1, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (2, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
             }
-        }
-        Ok(Helpful {
-            switch: p_.required("--switch", switch)?,
-            src: p_.optional("src", src.1)?,
-            extra: p_.optional("extra", extra.1)?,
-            subcommand: match state_ {
-                1 => HelpfulCmd::Sub(Sub { flag: p_.optional("--flag", sub__flag)?.is_some() }),
-                _ => return Err(p_.subcommand_required()),
-            },
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    1 => Self::HELP_CLEAN__,
+                    2 => Self::HELP_SUB__,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            p_.check_count("--switch", switch.len(), 1, Some(1));
+            p_.check_count("--jobs <n>", jobs.len(), 0, Some(1));
+            p_.check_count("<src>", src.1.len(), 0, Some(1));
+            p_.check_count("<extra>", extra.1.len(), 0, Some(1));
+            if state_ == 0 {
+                p_.subcommand_required();
+            }
+            if matches!(state_, 2) {
+                p_.check_count("--flag", sub__flag.len(), 0, Some(1));
+            }
+            p_.finish_checks()?;
+            Ok(Helpful {
+                switch: (),
+                jobs: jobs.pop(),
+                src: src.1.pop(),
+                extra: extra.1.pop(),
+                subcommand: match state_ {
+                    1 => HelpfulCmd::Clean(Clean {}),
+                    2 => HelpfulCmd::Sub(Sub { flag: !sub__flag.is_empty() }),
+                    _ => unreachable!(),
+                },
+            })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("helpful", "helpful [src] [extra] -s [-j <n>] [-h] <COMMAND>", true),
+                1 => ("helpful clean", "helpful clean", true),
+                2 => ("helpful sub", "helpful sub [-f]", true),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
         })
     }
 }
 impl Helpful {
-    const HELP_SUB__: &'static str = "Usage: sub [-f]
+    const HELP_CLEAN__: &'static str = "Usage: helpful clean
+
+Remove build artifacts.
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_CLEAN__: &'static str = "Usage: helpful clean
+
+Remove build artifacts.
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SUB__: &'static str = "Usage: helpful sub [-f]
 
 And even a subcommand!
 
 Options:
   -f, --flag           With an optional flag. This has a really long
-description which spans multiple lines.
+                       description which spans multiple lines.
 
 Commands:
   help                 Print this message or the help of the given subcommand(s)";
-    const HELP_: &'static str = "Usage: helpful [src] [extra] -s [-h] <COMMAND>
+    const HELP_SHORT_SUB__: &'static str = "Usage: helpful sub [-f]
+
+And even a subcommand!
+
+Options:
+  -f, --flag           With an optional flag. This has a really long description which spans multiple lines.
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_: &'static str = "Usage: helpful [src] [extra] -s [-j <n>] [-h] <COMMAND>
 
 Does stuff
 
@@ -122,15 +267,157 @@ Arguments:
   [src]                With an arg.
   [extra]              Another arg.
 
-This time, we provide some extra info about the
-arg. Maybe some caveats, or what kinds of
-values are accepted.
+                       This time, we provide some extra info about the
+                       arg. Maybe some caveats, or what kinds of
+                       values are accepted.
+
+Options:
+  -s, --switch         And a switch.
+  -h, --help           Prints help
+
+Advanced options:
+  -j, --jobs <n>       Number of threads.
+  clean                Remove build artifacts.
+
+Commands:
+  sub                  And even a subcommand!
+  help                 Print this message or the help of the given subcommand(s)
+
+Examples:
+  helpful -s src
+      Run on the `src` directory.
+  helpful -s
+
+See also: \"helpful sub --help\".";
+    const HELP_SHORT_: &'static str = "Usage: helpful [src] [extra] -s [-j <n>] [-h] <COMMAND>
+
+Does stuff
+
+Arguments:
+  [src]                With an arg.
+  [extra]              Another arg.
 
 Options:
   -s, --switch         And a switch.
   -h, --help           Prints help
 
+Advanced options:
+  -j, --jobs <n>       Number of threads.
+  clean                Remove build artifacts.
+
 Commands:
   sub                  And even a subcommand!
   help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"helpful\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": \"Does stuff\\n\\nHelpful stuff.\",
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"src\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"type\": \"PathBuf\",
+      \"doc\": \"With an arg.\"
+    },
+    {
+      \"name\": \"extra\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"type\": \"String\",
+      \"doc\": \"Another arg.\\n\\nThis time, we provide some extra info about the\\narg. Maybe some caveats, or what kinds of\\nvalues are accepted.\"
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"switch\",
+      \"short\": \"s\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"And a switch.\",
+      \"section\": null
+    },
+    {
+      \"long\": \"jobs\",
+      \"short\": \"j\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"n\",
+        \"type\": \"u32\"
+      },
+      \"delimiter\": null,
+      \"doc\": \"Number of threads.\",
+      \"section\": \"Advanced options\"
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [
+    {
+      \"name\": \"clean\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": \"Remove build artifacts.\",
+      \"section\": \"Advanced options\",
+      \"args\": [],
+      \"flags\": [],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    },
+    {
+      \"name\": \"sub\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": \"And even a subcommand!\",
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"flag\",
+          \"short\": \"f\",
+          \"arity\": \"optional\",
+          \"min\": 0,
+          \"max\": 1,
+          \"value\": null,
+          \"delimiter\": null,
+          \"doc\": \"With an optional flag. This has a really long\\ndescription which spans multiple lines.\",
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    }
+  ],
+  \"examples\": [
+    {
+      \"command\": \"helpful -s src\",
+      \"doc\": \"Run on the `src` directory.\"
+    },
+    {
+      \"command\": \"helpful -s\",
+      \"doc\": null
+    }
+  ],
+  \"after_help\": \"See also: \\\"helpful sub --help\\\".\"
+}";
 }
@@ -3,6 +3,12 @@ mod smoke;
 mod repeated_pos;
 mod subcommands;
 mod help;
+mod delimited;
+mod bounds;
+mod attrs;
+mod naming;
+mod trailing;
+mod default_args;
 
 use std::{ffi::OsString, fmt};
 
@@ -25,6 +31,22 @@ where
     }
 }
 
+fn check_roundtrip<A>(
+    parse: impl Fn(Vec<OsString>) -> xflags::Result<A>,
+    unparse: impl Fn(&A) -> Vec<OsString>,
+    args: &str,
+    expect: Expect,
+) where
+    A: fmt::Debug,
+{
+    let args = args.split_ascii_whitespace().map(OsString::from).collect::<Vec<_>>();
+    let flags = parse(args).unwrap();
+    let args = unparse(&flags);
+    expect.assert_debug_eq(&args);
+    let reparsed = parse(args).unwrap();
+    assert_eq!(format!("{flags:?}"), format!("{reparsed:?}"));
+}
+
 #[test]
 fn empty() {
     check(
@@ -77,44 +99,56 @@ fn smoke() {
     check(
         smoke::RustAnalyzer::from_vec,
         "-n 92 --werbose",
-        expect!["Unknown flag: `--werbose`. Use `help` for more information"],
+        expect!["Unknown flag: `--werbose`. Use `--help` for more information"],
     );
     check(
         smoke::RustAnalyzer::from_vec,
         "",
-        expect!["Flag is required: `--number`. Use `help` for more information"],
+        expect![[r#"
+            Invalid arguments:
+              Flag is required: `--number <n>`
+              Argument is required: `<workspace>`
+            Use `--help` for more information"#]],
     );
     check(
         smoke::RustAnalyzer::from_vec,
         ".",
-        expect!["Flag is required: `--number`. Use `help` for more information"],
+        expect!["Flag is required: `--number <n>`. Use `--help` for more information"],
     );
     check(smoke::RustAnalyzer::from_vec, "-n", expect![[r#"expected a value for `-n`"#]]);
     check(
         smoke::RustAnalyzer::from_vec,
         "-n 92",
-        expect!["Flag is required: `workspace`. Use `help` for more information"],
+        expect!["Argument is required: `<workspace>`. Use `--help` for more information"],
     );
     check(
         smoke::RustAnalyzer::from_vec,
         "-n lol",
-        expect!["Can't parse `-n`, invalid digit found in string"],
+        expect!["Invalid value `lol` for `-n` at argument 2: expected `u32`, a non-negative whole number"],
     );
     check(
         smoke::RustAnalyzer::from_vec,
         "-n 1 -n 2 .",
-        expect!["Flag specified more than once: `--number`"],
+        expect!["Flag specified more than once: `--number <n>`"],
     );
     check(
         smoke::RustAnalyzer::from_vec,
         "-n 1 . 92 lol",
-        expect!["Unknown command: `lol`. Use `help` for more information"],
+        expect!["Unknown command: `lol`. Use `--help` for more information"],
     );
     check(
         smoke::RustAnalyzer::from_vec,
         "-n 1 . --emoji --emoji",
         expect!["Flag specified more than once: `--emoji`"],
     );
+    check(
+        smoke::RustAnalyzer::from_vec,
+        "-n 1 -n 2 . 1 --emoji --emoji",
+        expect![[r#"
+            Invalid arguments:
+              Flag specified more than once: `--number <n>`
+              Flag specified more than once: `--emoji`"#]],
+    );
 }
 
 #[test]
@@ -141,6 +175,357 @@ fn repeated_argument() {
     );
 }
 
+#[test]
+fn trailing_arguments() {
+    check(
+        trailing::Trailing::from_vec,
+        "x a b -r c 1",
+        expect![[r#"
+            Trailing {
+                first: "x",
+                sources: [
+                    "a",
+                    "b",
+                ],
+                dest: "c",
+                mode: 1,
+                recursive: true,
+            }
+        "#]],
+    );
+    check(
+        trailing::Trailing::from_vec,
+        "x a 1",
+        expect!["Argument is required: `<sources>...`. Use `--help` for more information"],
+    );
+    check(
+        trailing::Trailing::from_vec,
+        "x a b c",
+        expect!["Invalid value `c` for `mode` at argument 4: expected `u32`, a non-negative whole number"],
+    );
+    check_roundtrip(
+        trailing::Trailing::from_vec,
+        trailing::Trailing::to_args,
+        "x -- -a b 1",
+        expect![[r#"
+            [
+                "x",
+                "--",
+                "-a",
+                "b",
+                "1",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn delimited() {
+    check(
+        delimited::Delimited::from_vec,
+        r"-F a\,b,c\\,d\e\ --paths C:\dir,\\srv\share",
+        expect![[r#"
+            Delimited {
+                features: [
+                    "a,b",
+                    "c\\",
+                    "d\\e\\",
+                ],
+                jobs: [],
+                paths: [
+                    "C:\\dir",
+                    "\\\\srv\\share",
+                ],
+                targets: [],
+            }
+        "#]],
+    );
+    check(
+        delimited::Delimited::from_vec,
+        "--targets a,b",
+        expect![[r#"
+            Delimited {
+                features: [],
+                jobs: [],
+                paths: [],
+                targets: [
+                    "a",
+                    "b",
+                ],
+            }
+        "#]],
+    );
+    check(
+        delimited::Delimited::from_vec,
+        "--targets a --targets b",
+        expect!["Flag specified more than once: `--targets <target>,...`"],
+    );
+    check(
+        delimited::Delimited::from_vec,
+        "--features a,b -F c --jobs 1:2 --paths x",
+        expect![[r#"
+            Delimited {
+                features: [
+                    "a",
+                    "b",
+                    "c",
+                ],
+                jobs: [
+                    1,
+                    2,
+                ],
+                paths: [
+                    "x",
+                ],
+                targets: [],
+            }
+        "#]],
+    );
+    check(
+        delimited::Delimited::from_vec,
+        "",
+        expect![[r#"
+            Delimited {
+                features: [],
+                jobs: [],
+                paths: [],
+                targets: [],
+            }
+        "#]],
+    );
+    check(
+        delimited::Delimited::from_vec,
+        "--jobs 1:x",
+        expect!["Invalid value `x` for `--jobs` at argument 2: expected `u32`, a non-negative whole number"],
+    );
+    check(
+        delimited::Delimited::from_vec,
+        "--jobs 1 --jobs 2",
+        expect!["Flag specified more than once: `--jobs <n>:...`"],
+    );
+}
+
+#[test]
+fn bounds() {
+    check(
+        bounds::Bounds::from_vec,
+        "a b -v -v --point 1 --point 2",
+        expect![[r#"
+            Bounds {
+                files: [
+                    "a",
+                    "b",
+                ],
+                verbose: 2,
+                point: [
+                    1,
+                    2,
+                ],
+            }
+        "#]],
+    );
+    check(
+        bounds::Bounds::from_vec,
+        "--point 1 --point 2",
+        expect!["Argument is required: `<files>...`. Use `--help` for more information"],
+    );
+    check(
+        bounds::Bounds::from_vec,
+        "a --point 1",
+        expect!["Flag must be specified at least 2 times: `--point <coord>`. Use `--help` for more information"],
+    );
+    check(
+        bounds::Bounds::from_vec,
+        "a --point 1 --point 2 -v -v -v -v",
+        expect!["Flag specified more than 3 times: `--verbose`"],
+    );
+}
+
+#[test]
+fn derives() {
+    let flags = attrs::Attrs::from_str_args(&[]).unwrap();
+    assert_eq!(flags.clone(), attrs::Attrs::default());
+
+    let flags = attrs::Attrs::from_str_args(&["check"]).unwrap();
+    assert_eq!(flags.subcommand, attrs::AttrsCmd::Check(attrs::Check));
+}
+
+#[test]
+fn doc_escapes() {
+    let help = attrs::Attrs::from_str_args(&["--help"]).unwrap_err().to_string();
+    let line = help.lines().find(|it| it.starts_with("  --name")).unwrap();
+    expect![[r#"  --name <name>        Name, like "x" or `C:\x`."#]].assert_eq(line);
+}
+
+#[test]
+fn naming() {
+    check(
+        naming::Naming::from_vec,
+        "src -t lib build --crate a --crate b",
+        expect![[r#"
+            Naming {
+                target: "src",
+                kind: Some(
+                    "lib",
+                ),
+                ref: None,
+                command: Build(
+                    Build {
+                        crates: [
+                            "a",
+                            "b",
+                        ],
+                    },
+                ),
+            }
+        "#]],
+    );
+
+    // `--` lasts until the name of a subcommand.
+    check(
+        naming::Naming::from_vec,
+        "-- -x build --crate a",
+        expect![[r#"
+            Naming {
+                target: "-x",
+                kind: None,
+                ref: None,
+                command: Build(
+                    Build {
+                        crates: [
+                            "a",
+                        ],
+                    },
+                ),
+            }
+        "#]],
+    );
+
+    let flags = naming::Naming::from_str_args(&["src", "test", "--match", "foo"]).unwrap();
+    match flags.command {
+        naming::NamingCommand::Test(it) => assert_eq!(it.r#match.as_deref(), Some("foo")),
+        naming::NamingCommand::Build(_) => panic!("expected `test`"),
+    }
+
+    let flags = naming::Naming::from_str_args(&["--ref", "main", "src", "build"]).unwrap();
+    assert_eq!(flags.r#ref.as_deref(), Some("main"));
+}
+
+#[test]
+fn spec_json() {
+    expect![[r#"
+        {
+          "name": "attrs",
+          "aliases": [],
+          "default": false,
+          "doc": null,
+          "section": null,
+          "args": [],
+          "flags": [
+            {
+              "long": "name",
+              "short": null,
+              "arity": "optional",
+              "min": 0,
+              "max": 1,
+              "value": {
+                "name": "name",
+                "type": "String"
+              },
+              "delimiter": null,
+              "doc": "Name, like \"x\" or `C:\\x`.",
+              "section": null
+            },
+            {
+              "long": "help",
+              "short": "h",
+              "arity": "optional",
+              "min": 0,
+              "max": 1,
+              "value": null,
+              "delimiter": null,
+              "doc": "Prints help",
+              "section": null
+            }
+          ],
+          "subcommands": [
+            {
+              "name": "run",
+              "aliases": [],
+              "default": true,
+              "doc": null,
+              "section": null,
+              "args": [],
+              "flags": [
+                {
+                  "long": "verbose",
+                  "short": "v",
+                  "arity": "repeated",
+                  "min": 0,
+                  "max": null,
+                  "value": null,
+                  "delimiter": null,
+                  "doc": null,
+                  "section": null
+                }
+              ],
+              "subcommands": [],
+              "examples": [],
+              "after_help": null
+            },
+            {
+              "name": "check",
+              "aliases": [],
+              "default": false,
+              "doc": null,
+              "section": null,
+              "args": [],
+              "flags": [],
+              "subcommands": [],
+              "examples": [],
+              "after_help": null
+            }
+          ],
+          "examples": [],
+          "after_help": null
+        }"#]]
+    .assert_eq(attrs::Attrs::SPEC_JSON);
+}
+
+#[test]
+fn short_and_long_help() {
+    check(
+        help::Helpful::from_vec,
+        "-h",
+        expect![[r#"
+            Usage: helpful [src] [extra] -s [-j <n>] [-h] <COMMAND>
+
+            Does stuff
+
+            Arguments:
+              [src]                With an arg.
+              [extra]              Another arg.
+
+            Options:
+              -s, --switch         And a switch.
+              -h, --help           Prints help
+
+            Advanced options:
+              -j, --jobs <n>       Number of threads.
+              clean                Remove build artifacts.
+
+            Commands:
+              sub                  And even a subcommand!
+              help                 Print this message or the help of the given subcommand(s)"#]],
+    );
+
+    let long = help::Helpful::from_str_args(&["--help"]).unwrap_err().to_string();
+    assert!(long.contains("This time, we provide some extra info"));
+    assert!(long.contains("Examples:"));
+    let help = help::Helpful::from_str_args(&["help"]).unwrap_err().to_string();
+    assert_eq!(help, long);
+}
+
 #[test]
 fn subcommands() {
     check(
@@ -250,7 +635,7 @@ fn subcommand_flag_inheritance() {
     check(
         subcommands::RustAnalyzer::from_vec,
         "analysis-stats --verbose --dir .",
-        expect!["Unknown flag: `--dir`. Use `help` for more information"],
+        expect!["Unknown flag: `--dir`. Use `--help` for more information"],
     );
     check(
         subcommands::RustAnalyzer::from_vec,
@@ -259,6 +644,54 @@ fn subcommand_flag_inheritance() {
     );
 }
 
+#[test]
+fn default_subcommand_args() {
+    check(
+        default_args::DefaultArgs::from_vec,
+        "--release a b",
+        expect![[r#"
+            DefaultArgs {
+                path: Some(
+                    "a",
+                ),
+                subcommand: Run(
+                    Run {
+                        args: [
+                            "b",
+                        ],
+                        release: true,
+                    },
+                ),
+            }
+        "#]],
+    );
+    check(
+        default_args::DefaultArgs::from_vec,
+        "a --release b c",
+        expect![[r#"
+            DefaultArgs {
+                path: Some(
+                    "a",
+                ),
+                subcommand: Run(
+                    Run {
+                        args: [
+                            "b",
+                            "c",
+                        ],
+                        release: true,
+                    },
+                ),
+            }
+        "#]],
+    );
+    check(
+        default_args::DefaultArgs::from_vec,
+        "check --package x a",
+        expect!["Unknown command: `a`. Use `help` for more information"],
+    );
+}
+
 #[test]
 fn edge_cases() {
     check(
@@ -311,7 +744,7 @@ fn edge_cases() {
     check(
         repeated_pos::RepeatedPos::from_vec,
         "pos 1 prog -j",
-        expect!["Unknown flag: `-j`. Use `help` for more information"],
+        expect!["Unknown flag: `-j`. Use `--help` for more information"],
     );
     check(
         repeated_pos::RepeatedPos::from_vec,
@@ -332,3 +765,450 @@ fn edge_cases() {
         "#]],
     );
 }
+
+#[test]
+fn help_argument() {
+    let help = |args: &[&str]| {
+        let err = subcommands::RustAnalyzer::from_str_args(args).unwrap_err();
+        err.to_string().lines().next().unwrap().to_string()
+    };
+    expect!["Usage: rust-analyzer [-v]... [-h] <COMMAND>"].assert_eq(&help(&["help"]));
+    expect!["Usage: rust-analyzer server [--dir <path>] [--log] <COMMAND>"]
+        .assert_eq(&help(&["help", "server"]));
+    expect!["Usage: rust-analyzer server watch"].assert_eq(&help(&["server", "help", "watch"]));
+
+    check(
+        subcommands::RustAnalyzer::from_vec,
+        "analysis-stats help",
+        expect![[r#"
+            RustAnalyzer {
+                verbose: 0,
+                subcommand: AnalysisStats(
+                    AnalysisStats {
+                        path: "help",
+                        parallel: false,
+                    },
+                ),
+            }
+        "#]],
+    );
+    check(
+        smoke::RustAnalyzer::from_vec,
+        "help -n 1 --log-file help",
+        expect![[r#"
+            RustAnalyzer {
+                workspace: "help",
+                jobs: None,
+                log_file: Some(
+                    "help",
+                ),
+                verbose: 0,
+                number: 1,
+                data: [],
+                emoji: false,
+            }
+        "#]],
+    );
+    check(
+        help::Helpful::from_vec,
+        "-s -- help sub",
+        expect![[r#"
+            Helpful {
+                src: Some(
+                    "help",
+                ),
+                extra: None,
+                switch: (),
+                jobs: None,
+                subcommand: Sub(
+                    Sub {
+                        flag: false,
+                    },
+                ),
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn value_errors() {
+    check(smoke::RustAnalyzer::from_vec, "ws -n 4294967296", expect!["Invalid value `4294967296` for `-n` at argument 3: expected `u32`, a number between 0 and 4294967295"]);
+    check(smoke::RustAnalyzer::from_vec, "ws -n -1", expect!["Invalid value `-1` for `-n` at argument 3: expected `u32`, a number between 0 and 4294967295"]);
+    check(smoke::RustAnalyzer::from_vec, "ws -n 1 1.5", expect!["Invalid value `1.5` for `jobs` at argument 4: expected `u32`, a non-negative whole number"]);
+    check(delimited::Delimited::from_vec, "-F a --jobs 1:2: --paths x", expect!["Invalid value `` for `--jobs` at argument 4: expected `u32`, a non-negative whole number"]);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        let args = vec!["ws".into(), "-n".into(), OsString::from_vec(vec![b'1', 0xff])];
+        check(
+            |_| smoke::RustAnalyzer::from_vec(args),
+            "",
+            expect![[
+                r#"Invalid value `1\xFF` for `-n` at argument 3: expected `u32`, invalid UTF-8"#
+            ]],
+        );
+    }
+}
+
+#[test]
+fn arg_sources() {
+    let flags = smoke::RustAnalyzer::from_str_args(&["ws", "-n", "1"]).unwrap();
+    assert_eq!(flags.workspace, std::path::PathBuf::from("ws"));
+
+    let args = vec![String::from("ws"), String::from("-n"), String::from("1")];
+    let flags = smoke::RustAnalyzer::from_args(args).unwrap();
+    assert_eq!(flags.number, 1);
+
+    let flags = smoke::RustAnalyzer::from_argv(["rust-analyzer", "ws", "-n", "2"]).unwrap();
+    assert_eq!((flags.workspace.to_str(), flags.number), (Some("ws"), 2));
+}
+
+#[test]
+fn command_line() {
+    check(
+        |_| {
+            smoke::RustAnalyzer::from_command_line(
+                r#"'my ws' -n 1 --data "a \"b\" \c" --data '' --data x\ y"#,
+            )
+        },
+        "",
+        expect![[r#"
+            RustAnalyzer {
+                workspace: "my ws",
+                jobs: None,
+                log_file: None,
+                verbose: 0,
+                number: 1,
+                data: [
+                    "a \"b\" \\c",
+                    "",
+                    "x y",
+                ],
+                emoji: false,
+            }
+        "#]],
+    );
+    check(
+        |_| smoke::RustAnalyzer::from_command_line("ws -n 1 --data 'x"),
+        "",
+        expect!["Unmatched `'` in command line"],
+    );
+    check(
+        |_| smoke::RustAnalyzer::from_command_line(r#"ws -n "1"#),
+        "",
+        expect![[r#"Unmatched `"` in command line"#]],
+    );
+    check(
+        |_| smoke::RustAnalyzer::from_command_line(r"ws -n 1 \"),
+        "",
+        expect!["Trailing backslash in command line"],
+    );
+}
+
+#[test]
+fn repl() {
+    let input = "server --log\n\nfoo\nserver watch help\nanalysis-stats 'my path'\n";
+    let mut repl = xflags::Repl::with_io(input.as_bytes(), Vec::new(), "> ");
+    let mut cmds = Vec::new();
+    while let Some(cmd) = repl.next(subcommands::RustAnalyzer::from_command_line) {
+        cmds.push(cmd.unwrap().subcommand);
+    }
+    expect![[r#"
+        [
+            Server(
+                Server {
+                    dir: None,
+                    subcommand: Launch(
+                        Launch {
+                            log: true,
+                        },
+                    ),
+                },
+            ),
+            AnalysisStats(
+                AnalysisStats {
+                    path: "my path",
+                    parallel: false,
+                },
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&cmds);
+    expect![[r#"
+        > > > Unknown command: `foo`. Use `help` for more information
+        > Usage: rust-analyzer server watch
+        Commands:
+          help                 Print this message or the help of the given subcommand(s)
+        > > "#]]
+    .assert_eq(&String::from_utf8(repl.into_output()).unwrap());
+
+    // Input errors are not mistaken for the end of input.
+    let mut repl = xflags::Repl::with_io(&b"\xff\n"[..], Vec::new(), "> ");
+    let err = repl.next(subcommands::RustAnalyzer::from_command_line).unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn partial() {
+    check(
+        subcommands::RustAnalyzer::from_vec_partial,
+        "-v --plugin x server --dir . -- --log y",
+        expect![[r#"
+            (
+                RustAnalyzer {
+                    verbose: 1,
+                    subcommand: Server(
+                        Server {
+                            dir: Some(
+                                ".",
+                            ),
+                            subcommand: Launch(
+                                Launch {
+                                    log: false,
+                                },
+                            ),
+                        },
+                    ),
+                },
+                [
+                    "--plugin",
+                    "x",
+                    "--",
+                    "--log",
+                    "y",
+                ],
+            )
+        "#]],
+    );
+    check(
+        subcommands::RustAnalyzer::from_vec_partial,
+        "analysis-stats . --plugin",
+        expect![[r#"
+            (
+                RustAnalyzer {
+                    verbose: 0,
+                    subcommand: AnalysisStats(
+                        AnalysisStats {
+                            path: ".",
+                            parallel: false,
+                        },
+                    ),
+                },
+                [
+                    "--plugin",
+                ],
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn to_args() {
+    check_roundtrip(
+        smoke::RustAnalyzer::from_vec,
+        smoke::RustAnalyzer::to_args,
+        "-n 92 -v -v --data x --data -y -- -workspace",
+        expect![[r#"
+            [
+                "--verbose",
+                "--verbose",
+                "--number",
+                "92",
+                "--data",
+                "x",
+                "--data",
+                "-y",
+                "--",
+                "-workspace",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        subcommands::RustAnalyzer::from_vec,
+        subcommands::RustAnalyzer::to_args,
+        "server --log --dir . -v",
+        expect![[r#"
+            [
+                "--verbose",
+                "server",
+                "--dir",
+                ".",
+                "--log",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        subcommands::RustAnalyzer::from_vec,
+        subcommands::RustAnalyzer::to_args,
+        "analysis-stats . --parallel",
+        expect![[r#"
+            [
+                "analysis-stats",
+                "--parallel",
+                ".",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        repeated_pos::RepeatedPos::from_vec,
+        repeated_pos::RepeatedPos::to_args,
+        "a 1 c d e",
+        expect![[r#"
+            [
+                "a",
+                "1",
+                "c",
+                "d",
+                "e",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        naming::Naming::from_vec,
+        naming::Naming::to_args,
+        "--type lib . build --crate a",
+        expect![[r#"
+            [
+                "--type",
+                "lib",
+                ".",
+                "build",
+                "--crate",
+                "a",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        delimited::Delimited::from_vec,
+        delimited::Delimited::to_args,
+        "-F a,b --features c --jobs 1:2",
+        expect![[r#"
+            [
+                "--features",
+                "a,b,c",
+                "--jobs",
+                "1:2",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        naming::Naming::from_vec,
+        naming::Naming::to_args,
+        "-- -x build --crate a",
+        expect![[r#"
+            [
+                "--",
+                "-x",
+                "build",
+                "--crate",
+                "a",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        naming::Naming::from_vec,
+        naming::Naming::to_args,
+        "-- help build --crate a",
+        expect![[r#"
+            [
+                "--",
+                "help",
+                "build",
+                "--crate",
+                "a",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        default_args::DefaultArgs::from_vec,
+        default_args::DefaultArgs::to_args,
+        "--release a b",
+        expect![[r#"
+            [
+                "a",
+                "--release",
+                "b",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        subcommands::RustAnalyzer::from_vec,
+        subcommands::RustAnalyzer::to_args,
+        "-v analysis-stats -- -x",
+        expect![[r#"
+            [
+                "--verbose",
+                "analysis-stats",
+                "--",
+                "-x",
+            ]
+        "#]],
+    );
+    check_roundtrip(
+        delimited::Delimited::from_vec,
+        delimited::Delimited::to_args,
+        r"-F a\,b --features c\\,d --paths C:\dir,\\srv\share",
+        expect![[r#"
+            [
+                "--features",
+                "a\\,b,c\\\\,d",
+                "--paths",
+                "C:\\dir,\\\\srv\\share",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn error_usage() {
+    let usage = |args: &[&str]| {
+        let err = subcommands::RustAnalyzer::from_str_args(args).unwrap_err();
+        format!("{:?}\n{:?}", err.command(), err.usage())
+    };
+    expect![[r#"
+        Some("rust-analyzer")
+        Some("rust-analyzer [-v]... [-h] <COMMAND>")"#]]
+    .assert_eq(&usage(&["--lol"]));
+    expect![[r#"
+        Some("rust-analyzer analysis-stats")
+        Some("rust-analyzer analysis-stats <path> [--parallel]")"#]]
+    .assert_eq(&usage(&["analysis-stats"]));
+    expect![[r#"
+        Some("rust-analyzer server")
+        Some("rust-analyzer server [--dir <path>] [--log] <COMMAND>")"#]]
+    .assert_eq(&usage(&["server", "--dir"]));
+    expect![[r#"
+        Some("rust-analyzer server watch")
+        Some("rust-analyzer server watch")"#]]
+    .assert_eq(&usage(&["server", "watch", "--lol"]));
+    expect![[r#"
+        None
+        None"#]]
+    .assert_eq(&usage(&["help"]));
+}
+
+#[test]
+fn program_name() {
+    let first_line = |err: xflags::Error| err.to_string().lines().next().unwrap().to_string();
+
+    let err = subcommands::RustAnalyzer::from_argv(["/usr/bin/ra", "server", "watch", "-h"]);
+    expect!["Usage: ra server watch"].assert_eq(&first_line(err.unwrap_err()));
+    let err = subcommands::RustAnalyzer::from_str_args(&["server", "watch", "-h"]);
+    expect!["Usage: rust-analyzer server watch"].assert_eq(&first_line(err.unwrap_err()));
+    let err = subcommands::RustAnalyzer::from_argv(["ra", "analysis-stats"]).unwrap_err();
+    expect![[r#"
+        Some("ra analysis-stats")
+        Some("ra analysis-stats <path> [--parallel]")"#]]
+    .assert_eq(&format!("{:?}\n{:?}", err.command(), err.usage()));
+
+    let err = naming::Naming::from_argv(["/usr/bin/naming", "--help"]);
+    expect!["Usage: nm <path> [-t <ty>] [--ref <name>] [-h] <COMMAND>"]
+        .assert_eq(&first_line(err.unwrap_err()));
+    let err = naming::Naming::from_argv(["/usr/bin/naming", ".", "build", "--lol"]).unwrap_err();
+    expect![[r#"
+        Some("nm build")
+        Some("nm build [--crate <name>]...")"#]]
+    .assert_eq(&format!("{:?}\n{:?}", err.command(), err.usage()));
+}
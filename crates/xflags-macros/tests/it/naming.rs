@@ -0,0 +1,426 @@
+#![allow(dead_code)] // unused fields
+#[allow(unused)]
+use std::{ffi::OsString, path::PathBuf};
+
+#[derive(Debug)]
+pub(crate) struct Naming {
+    pub(crate) target: PathBuf,
+
+    pub(crate) kind: Option<String>,
+    pub(crate) r#ref: Option<String>,
+    pub(crate) command: NamingCommand,
+}
+
+#[derive(Debug)]
+pub(crate) enum NamingCommand {
+    Build(Build),
+    Test(Test),
+}
+
+#[derive(Debug)]
+pub(crate) struct Build {
+    pub(crate) crates: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Test {
+    pub(crate) r#match: Option<String>,
+}
+
+impl Naming {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
+}
+
+impl Naming {
+    fn from_env_or_exit_() -> Self {
+        Self::from_env_().unwrap_or_else(|err| err.exit())
+    }
+    fn from_env_() -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_env();
+        Self::parse_(&mut p)
+    }
+    fn from_vec_(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new(args);
+        Self::parse_(&mut p)
+    }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
+}
+
+impl Naming {
+    fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
+        #![allow(non_snake_case, unused_mut)]
+        let mut kind = Vec::new();
+        let mut r#ref = Vec::new();
+        let mut target = (false, Vec::new());
+        let mut build__crates = Vec::new();
+        let mut test__match = Vec::new();
+
+        let mut state_ = 0u8;
+        let mut help_ = false;
+        let mut parse_ =
+            || -> xflags::Result<Self> {
+                while let Some(arg_) = p_.pop_flag() {
+                    match arg_ {
+                        Ok(flag_) => match (state_, flag_.as_str()) {
+                            (0, "--help") => return Err(p_.help(Self::HELP_)),
+                            (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                            (0..=2, "--type" | "-t") => {
+                                kind.push(p_.next_value_from_str::<String>(&flag_, "String")?)
+                            }
+                            (0..=2, "--ref") => {
+                                r#ref.push(p_.next_value_from_str::<String>(&flag_, "String")?)
+                            }
+                            (1, "--help") => return Err(p_.help(Self::HELP_BUILD__)),
+                            (1, "-h") => return Err(p_.help(Self::HELP_SHORT_BUILD__)),
+                            (1, "--crate") => build__crates
+                                .push(p_.next_value_from_str::<String>(&flag_, "String")?),
+                            (2, "--help") => return Err(p_.help(Self::HELP_TEST__)),
+                            (2, "-h") => return Err(p_.help(Self::HELP_SHORT_TEST__)),
+                            (2, "--match") => test__match
+                                .push(p_.next_value_from_str::<String>(&flag_, "String")?),
+                            _ => p_.unexpected_flag(flag_)?,
+                        },
+                        Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                            (0, "build") => {
+                                state_ = 1;
+                                p_.enter_subcommand();
+                            }
+                            (0, "test") => {
+                                state_ = 2;
+                                p_.enter_subcommand();
+                            }
+                            (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                            (0, _) => {
+                                if let (done_ @ false, buf_) = &mut target {
+                                    buf_.push(arg_.into());
+                                    *done_ = true;
+                                    continue;
+                                }
+                                p_.unexpected_arg(arg_)?;
+                            }
+                            (1, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                            (2, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                            _ => p_.unexpected_arg(arg_)?,
+                        },
+                    }
+                }
+                if help_ {
+                    let help_ = match state_ {
+                        0 => Self::HELP_,
+                        1 => Self::HELP_BUILD__,
+                        2 => Self::HELP_TEST__,
+                        _ => unreachable!(),
+                    };
+                    return Err(p_.help(help_));
+                }
+                p_.check_count("--type <ty>", kind.len(), 0, Some(1));
+                p_.check_count("--ref <name>", r#ref.len(), 0, Some(1));
+                p_.check_count("<path>", target.1.len(), 1, Some(1));
+                if state_ == 0 {
+                    p_.subcommand_required();
+                }
+                if matches!(state_, 2) {
+                    p_.check_count("--match <pattern>", test__match.len(), 0, Some(1));
+                }
+                p_.finish_checks()?;
+                Ok(Naming {
+                    kind: kind.pop(),
+                    r#ref: r#ref.pop(),
+                    target: target.1.pop().unwrap(),
+                    command: match state_ {
+                        1 => NamingCommand::Build(Build { crates: build__crates }),
+                        2 => NamingCommand::Test(Test { r#match: test__match.pop() }),
+                        _ => unreachable!(),
+                    },
+                })
+            };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("nm", "nm <path> [-t <ty>] [--ref <name>] [-h] <COMMAND>", true),
+                1 => ("nm build", "nm build [--crate <name>]...", true),
+                2 => ("nm test", "nm test [--match <pattern>]", true),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
+    }
+}
+impl Naming {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if let Some(it) = &self.kind {
+            w_.flag("--type");
+            w_.value(it);
+        }
+        if let Some(it) = &self.r#ref {
+            w_.flag("--ref");
+            w_.value(it);
+        }
+        w_.arg(&self.target, true);
+        match &self.command {
+            NamingCommand::Build(it) => {
+                w_.subcommand("build");
+                it.unparse_(w_);
+            }
+            NamingCommand::Test(it) => {
+                w_.subcommand("test");
+                it.unparse_(w_);
+            }
+        }
+    }
+}
+impl Build {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        for it in &self.crates {
+            w_.flag("--crate");
+            w_.value(it);
+        }
+    }
+}
+impl Test {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if let Some(it) = &self.r#match {
+            w_.flag("--match");
+            w_.value(it);
+        }
+    }
+}
+impl Naming {
+    const HELP_BUILD__: &'static str = "Usage: nm build [--crate <name>]...
+Options:
+  --crate <name>       
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_BUILD__: &'static str = "Usage: nm build [--crate <name>]...
+Options:
+  --crate <name>       
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_TEST__: &'static str = "Usage: nm test [--match <pattern>]
+Options:
+  --match <pattern>    
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_TEST__: &'static str = "Usage: nm test [--match <pattern>]
+Options:
+  --match <pattern>    
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_: &'static str = "Usage: nm <path> [-t <ty>] [--ref <name>] [-h] <COMMAND>
+Arguments:
+  <path>               
+
+Options:
+  -t, --type <ty>      
+  --ref <name>         
+  -h, --help           Prints help
+
+Commands:
+  build                
+  test                 
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_: &'static str = "Usage: nm <path> [-t <ty>] [--ref <name>] [-h] <COMMAND>
+Arguments:
+  <path>               
+
+Options:
+  -t, --type <ty>      
+  --ref <name>         
+  -h, --help           Prints help
+
+Commands:
+  build                
+  test                 
+  help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"naming\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"path\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"type\": \"PathBuf\",
+      \"doc\": null
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"type\",
+      \"short\": \"t\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"ty\",
+        \"type\": \"String\"
+      },
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"ref\",
+      \"short\": null,
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"name\",
+        \"type\": \"String\"
+      },
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [
+    {
+      \"name\": \"build\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"crate\",
+          \"short\": null,
+          \"arity\": \"repeated\",
+          \"min\": 0,
+          \"max\": null,
+          \"value\": {
+            \"name\": \"name\",
+            \"type\": \"String\"
+          },
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    },
+    {
+      \"name\": \"test\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"match\",
+          \"short\": null,
+          \"arity\": \"optional\",
+          \"min\": 0,
+          \"max\": 1,
+          \"value\": {
+            \"name\": \"pattern\",
+            \"type\": \"String\"
+          },
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    }
+  ],
+  \"examples\": [],
+  \"after_help\": null
+}";
+}
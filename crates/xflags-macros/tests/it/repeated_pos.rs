@@ -25,6 +25,46 @@ impl RepeatedPos {
     pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
         Self::from_vec_(args)
     }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
 }
 
 impl RepeatedPos {
@@ -39,58 +79,110 @@ impl RepeatedPos {
         let mut p = xflags::rt::Parser::new(args);
         Self::parse_(&mut p)
     }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
 }
 
 impl RepeatedPos {
     fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
         #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("RepeatedPos");
         let mut a = (false, Vec::new());
         let mut b = (false, Vec::new());
         let mut c = (false, Vec::new());
         let mut rest = (false, Vec::new());
 
         let mut state_ = 0u8;
-        while let Some(arg_) = p_.pop_flag() {
-            match arg_ {
-                Ok(flag_) => match (state_, flag_.as_str()) {
-                    (0, "--help" | "-h") => return Err(p_.help(Self::HELP_)),
-                    _ => return Err(p_.unexpected_flag(&flag_)),
-                },
-                Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
-                    (0, _) => {
-                        if let (done_ @ false, buf_) = &mut a {
-                            buf_.push(arg_.into());
-                            *done_ = true;
-                            continue;
-                        }
-                        if let (done_ @ false, buf_) = &mut b {
-                            buf_.push(p_.value_from_str::<u32>("b", arg_)?);
-                            *done_ = true;
-                            continue;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, _) => {
+                            if let (done_ @ false, buf_) = &mut a {
+                                buf_.push(arg_.into());
+                                *done_ = true;
+                                continue;
+                            }
+                            if let (done_ @ false, buf_) = &mut b {
+                                buf_.push(p_.value_from_str::<u32>("b", "u32", arg_)?);
+                                *done_ = true;
+                                continue;
+                            }
+                            if let (done_ @ false, buf_) = &mut c {
+                                buf_.push(arg_);
+                                *done_ = true;
+                                continue;
+                            }
+                            if let (false, buf_) = &mut rest {
+                                buf_.push(arg_);
+                                continue;
+                            }
+                            p_.unexpected_arg(arg_)?;
                         }
-                        if let (done_ @ false, buf_) = &mut c {
-                            buf_.push(arg_.into());
-                            *done_ = true;
-                            continue;
-                        }
-                        if let (false, buf_) = &mut rest {
-                            buf_.push(arg_.into());
-                            continue;
-                        }
-                        return Err(p_.unexpected_arg(arg_));
-                    }
-                    _ => return Err(p_.unexpected_arg(arg_)),
-                },
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
             }
-        }
-        Ok(RepeatedPos {
-            a: p_.required("a", a.1)?,
-            b: p_.optional("b", b.1)?,
-            c: p_.optional("c", c.1)?,
-            rest: rest.1,
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            p_.check_count("<a>", a.1.len(), 1, Some(1));
+            p_.check_count("<b>", b.1.len(), 0, Some(1));
+            p_.check_count("<c>", c.1.len(), 0, Some(1));
+            p_.finish_checks()?;
+            Ok(RepeatedPos { a: a.1.pop().unwrap(), b: b.1.pop(), c: c.1.pop(), rest: rest.1 })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("RepeatedPos", "RepeatedPos <a> [b] [c] [rest]... [-h]", false),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
         })
     }
 }
+impl RepeatedPos {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        w_.arg(&self.a, false);
+        if let Some(it) = &self.b {
+            w_.arg(it.to_string(), false);
+        }
+        if let Some(it) = &self.c {
+            w_.arg(it, false);
+        }
+        for it in &self.rest {
+            w_.arg(it, false);
+        }
+    }
+}
 impl RepeatedPos {
     const HELP_: &'static str = "Usage: RepeatedPos <a> [b] [c] [rest]... [-h]
 Arguments:
@@ -100,8 +192,71 @@ Arguments:
   [rest]...            
 
 Options:
-  -h, --help           Prints help
+  -h, --help           Prints help";
+    const HELP_SHORT_: &'static str = "Usage: RepeatedPos <a> [b] [c] [rest]... [-h]
+Arguments:
+  <a>                  
+  [b]                  
+  [c]                  
+  [rest]...            
 
-Commands:
-  help                 Print this message or the help of the given subcommand(s)";
+Options:
+  -h, --help           Prints help";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"RepeatedPos\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"a\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"type\": \"PathBuf\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"b\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"type\": \"u32\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"c\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"type\": \"OsString\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"rest\",
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": null,
+      \"type\": \"OsString\",
+      \"doc\": null
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [],
+  \"examples\": [],
+  \"after_help\": null
+}";
 }
@@ -2,11 +2,14 @@
 #[allow(unused)]
 use std::{ffi::OsString, path::PathBuf};
 
+/// LSP server for rust.
 #[derive(Debug)]
 pub struct RustAnalyzer {
     pub workspace: PathBuf,
+    /// Number of concurrent jobs.
     pub jobs: Option<u32>,
 
+    /// Path to log file. By default, logs go to stderr.
     pub log_file: Option<PathBuf>,
     pub verbose: u32,
     pub number: u32,
@@ -29,6 +32,46 @@ impl RustAnalyzer {
     pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
         Self::from_vec_(args)
     }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
 }
 
 impl RustAnalyzer {
@@ -43,11 +86,32 @@ impl RustAnalyzer {
         let mut p = xflags::rt::Parser::new(args);
         Self::parse_(&mut p)
     }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
 }
 
 impl RustAnalyzer {
     fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
         #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("rust-analyzer");
         let mut log_file = Vec::new();
         let mut verbose = Vec::new();
         let mut number = Vec::new();
@@ -57,44 +121,94 @@ impl RustAnalyzer {
         let mut jobs = (false, Vec::new());
 
         let mut state_ = 0u8;
-        while let Some(arg_) = p_.pop_flag() {
-            match arg_ {
-                Ok(flag_) => match (state_, flag_.as_str()) {
-                    (0, "--help" | "-h") => return Err(p_.help(Self::HELP_)),
-                    (0, "--log-file") => log_file.push(p_.next_value(&flag_)?.into()),
-                    (0, "--verbose" | "-v") => verbose.push(()),
-                    (0, "--number" | "-n") => number.push(p_.next_value_from_str::<u32>(&flag_)?),
-                    (0, "--data") => data.push(p_.next_value(&flag_)?.into()),
-                    (0, "--emoji") => emoji.push(()),
-                    _ => return Err(p_.unexpected_flag(&flag_)),
-                },
-                Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
-                    (0, _) => {
-                        if let (done_ @ false, buf_) = &mut workspace {
-                            buf_.push(arg_.into());
-                            *done_ = true;
-                            continue;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0, "--log-file") => log_file.push(p_.next_value(&flag_)?.into()),
+                        (0, "--verbose" | "-v") => verbose.push(()),
+                        (0, "--number" | "-n") => {
+                            number.push(p_.next_value_from_str::<u32>(&flag_, "u32")?)
                         }
-                        if let (done_ @ false, buf_) = &mut jobs {
-                            buf_.push(p_.value_from_str::<u32>("jobs", arg_)?);
-                            *done_ = true;
-                            continue;
+                        (0, "--data") => data.push(p_.next_value(&flag_)?),
+                        (0, "--emoji") => emoji.push(()),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, _) => {
+                            if let (done_ @ false, buf_) = &mut workspace {
+                                buf_.push(arg_.into());
+                                *done_ = true;
+                                continue;
+                            }
+                            if let (done_ @ false, buf_) = &mut jobs {
+                                buf_.push(p_.value_from_str::<u32>("jobs", "u32", arg_)?);
+                                *done_ = true;
+                                continue;
+                            }
+                            p_.unexpected_arg(arg_)?;
                         }
-                        return Err(p_.unexpected_arg(arg_));
-                    }
-                    _ => return Err(p_.unexpected_arg(arg_)),
-                },
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
             }
+            p_.check_count("--log-file <path>", log_file.len(), 0, Some(1));
+            p_.check_count("--number <n>", number.len(), 1, Some(1));
+            p_.check_count("--emoji", emoji.len(), 0, Some(1));
+            p_.check_count("<workspace>", workspace.1.len(), 1, Some(1));
+            p_.check_count("<jobs>", jobs.1.len(), 0, Some(1));
+            p_.finish_checks()?;
+            Ok(RustAnalyzer {
+                log_file: log_file.pop(),
+                verbose: verbose.len() as u32,
+                number: number.pop().unwrap(),
+                data,
+                emoji: !emoji.is_empty(),
+                workspace: workspace.1.pop().unwrap(),
+                jobs: jobs.1.pop(),
+            })
+        };
+        parse_().map_err(|err_| {
+let (command_, usage_, help_command_) = match state_ {
+0 => ("rust-analyzer", "rust-analyzer <workspace> [jobs] [--log-file <path>] [-v]... -n <n> [--data <value>]... [--emoji] [-h]", false),
+_ => unreachable!(),
+};
+p_.with_usage(err_, command_, usage_, help_command_)
+})
+    }
+}
+impl RustAnalyzer {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if let Some(it) = &self.log_file {
+            w_.flag("--log-file");
+            w_.value(it);
+        }
+        for _ in 0..self.verbose {
+            w_.flag("--verbose");
+        }
+        w_.flag("--number");
+        w_.value(self.number.to_string());
+        for it in &self.data {
+            w_.flag("--data");
+            w_.value(it);
+        }
+        if self.emoji {
+            w_.flag("--emoji");
+        }
+        w_.arg(&self.workspace, false);
+        if let Some(it) = &self.jobs {
+            w_.arg(it.to_string(), false);
         }
-        Ok(RustAnalyzer {
-            log_file: p_.optional("--log-file", log_file)?,
-            verbose: verbose.len() as u32,
-            number: p_.required("--number", number)?,
-            data: data,
-            emoji: p_.optional("--emoji", emoji)?.is_some(),
-            workspace: p_.required("workspace", workspace.1)?,
-            jobs: p_.optional("jobs", jobs.1)?,
-        })
     }
 }
 impl RustAnalyzer {
@@ -112,8 +226,125 @@ Options:
   -n, --number <n>     
   --data <value>       
   --emoji              
-  -h, --help           Prints help
+  -h, --help           Prints help";
+    const HELP_SHORT_: &'static str = "Usage: rust-analyzer <workspace> [jobs] [--log-file <path>] [-v]... -n <n> [--data <value>]... [--emoji] [-h]
 
-Commands:
-  help                 Print this message or the help of the given subcommand(s)";
+LSP server for rust.
+
+Arguments:
+  <workspace>          
+  [jobs]               Number of concurrent jobs.
+
+Options:
+  --log-file <path>    Path to log file. By default, logs go to stderr.
+  -v, --verbose        
+  -n, --number <n>     
+  --data <value>       
+  --emoji              
+  -h, --help           Prints help";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"rust-analyzer\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": \"LSP server for rust.\",
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"workspace\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"type\": \"PathBuf\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"jobs\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"type\": \"u32\",
+      \"doc\": \"Number of concurrent jobs.\"
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"log-file\",
+      \"short\": null,
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"path\",
+        \"type\": \"PathBuf\"
+      },
+      \"delimiter\": null,
+      \"doc\": \"Path to log file. By default, logs go to stderr.\",
+      \"section\": null
+    },
+    {
+      \"long\": \"verbose\",
+      \"short\": \"v\",
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": null,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"number\",
+      \"short\": \"n\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"value\": {
+        \"name\": \"n\",
+        \"type\": \"u32\"
+      },
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"data\",
+      \"short\": null,
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": null,
+      \"value\": {
+        \"name\": \"value\",
+        \"type\": \"OsString\"
+      },
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"emoji\",
+      \"short\": null,
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [],
+  \"examples\": [],
+  \"after_help\": null
+}";
 }
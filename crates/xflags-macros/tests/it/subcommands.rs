@@ -56,6 +56,46 @@ impl RustAnalyzer {
     pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
         Self::from_vec_(args)
     }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
 }
 
 impl RustAnalyzer {
@@ -70,11 +110,32 @@ impl RustAnalyzer {
         let mut p = xflags::rt::Parser::new(args);
         Self::parse_(&mut p)
     }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
 }
 
 impl RustAnalyzer {
     fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
         #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("rust-analyzer");
         let mut verbose = Vec::new();
         let mut server__dir = Vec::new();
         let mut server__launch__log = Vec::new();
@@ -82,85 +143,215 @@ impl RustAnalyzer {
         let mut analysis_stats__path = (false, Vec::new());
 
         let mut state_ = 0u8;
-        while let Some(arg_) = p_.pop_flag() {
-            match arg_ {
-                Ok(flag_) => match (state_, flag_.as_str()) {
-                    (0, "--help" | "-h") => return Err(p_.help(Self::HELP_)),
-                    (0 | 1 | 2 | 3 | 4, "--verbose" | "-v") => verbose.push(()),
-                    (1, "--help" | "-h") => return Err(p_.help(Self::HELP_SERVER__)),
-                    (1 | 2 | 3, "--dir") => server__dir.push(p_.next_value(&flag_)?.into()),
-                    (1, _) => {
-                        p_.push_back(Ok(flag_));
-                        state_ = 2;
-                    }
-                    (2, "--help" | "-h") => return Err(p_.help(Self::HELP_SERVER__LAUNCH__)),
-                    (2, "--log") => server__launch__log.push(()),
-                    (3, "--help" | "-h") => return Err(p_.help(Self::HELP_SERVER__WATCH__)),
-                    (4, "--help" | "-h") => return Err(p_.help(Self::HELP_ANALYSIS_STATS__)),
-                    (4, "--parallel") => analysis_stats__parallel.push(()),
-                    _ => return Err(p_.unexpected_flag(&flag_)),
-                },
-                Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
-                    (0, "server") => state_ = 1,
-                    (0, "analysis-stats") => state_ = 4,
-                    (0, "help") => return Err(p_.help(Self::HELP_)),
-                    (0, _) => {
-                        return Err(p_.unexpected_arg(arg_));
-                    }
-                    (1, "watch") => state_ = 3,
-                    (1, "help") => return Err(p_.help(Self::HELP_SERVER__)),
-                    (1, _) => {
-                        p_.push_back(Err(arg_));
-                        state_ = 2;
-                    }
-                    (2, "help") => return Err(p_.help(Self::HELP_SERVER__LAUNCH__)),
-                    (3, "help") => return Err(p_.help(Self::HELP_SERVER__WATCH__)),
-                    (4, _) => {
-                        if let (done_ @ false, buf_) = &mut analysis_stats__path {
-                            buf_.push(arg_.into());
-                            *done_ = true;
-                            continue;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0..=4, "--verbose" | "-v") => verbose.push(()),
+                        (1, "--help") => return Err(p_.help(Self::HELP_SERVER__)),
+                        (1, "-h") => return Err(p_.help(Self::HELP_SHORT_SERVER__)),
+                        (1..=3, "--dir") => server__dir.push(p_.next_value(&flag_)?.into()),
+                        (1, _) => {
+                            p_.push_back(Ok(flag_));
+                            state_ = 2;
+                        }
+                        (2, "--help") => return Err(p_.help(Self::HELP_SERVER__LAUNCH__)),
+                        (2, "-h") => return Err(p_.help(Self::HELP_SHORT_SERVER__LAUNCH__)),
+                        (2, "--log") => server__launch__log.push(()),
+                        (3, "--help") => return Err(p_.help(Self::HELP_SERVER__WATCH__)),
+                        (3, "-h") => return Err(p_.help(Self::HELP_SHORT_SERVER__WATCH__)),
+                        (4, "--help") => return Err(p_.help(Self::HELP_ANALYSIS_STATS__)),
+                        (4, "-h") => return Err(p_.help(Self::HELP_SHORT_ANALYSIS_STATS__)),
+                        (4, "--parallel") => analysis_stats__parallel.push(()),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, "server") => {
+                            state_ = 1;
+                            p_.enter_subcommand();
+                        }
+                        (0, "analysis-stats") => {
+                            state_ = 4;
+                            p_.enter_subcommand();
+                        }
+                        (0, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (0, _) => {
+                            p_.unexpected_arg(arg_)?;
                         }
-                        return Err(p_.unexpected_arg(arg_));
-                    }
-                    _ => return Err(p_.unexpected_arg(arg_)),
+                        (1, "watch") => {
+                            state_ = 3;
+                            p_.enter_subcommand();
+                        }
+                        (1, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (1, _) => {
+                            p_.push_back(Err(arg_));
+                            state_ = 2;
+                        }
+                        (2, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (3, "help") if !help_ && !p_.after_double_dash() => help_ = true,
+                        (4, _) => {
+                            if let (done_ @ false, buf_) = &mut analysis_stats__path {
+                                buf_.push(arg_.into());
+                                *done_ = true;
+                                continue;
+                            }
+                            p_.unexpected_arg(arg_)?;
+                        }
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    1 => Self::HELP_SERVER__,
+                    2 => Self::HELP_SERVER__LAUNCH__,
+                    3 => Self::HELP_SERVER__WATCH__,
+                    4 => Self::HELP_ANALYSIS_STATS__,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            state_ = if state_ == 1 { 2 } else { state_ };
+            if state_ == 0 {
+                p_.subcommand_required();
+            }
+            if matches!(state_, 1..=3) {
+                p_.check_count("--dir <path>", server__dir.len(), 0, Some(1));
+            }
+            if matches!(state_, 2) {
+                p_.check_count("--log", server__launch__log.len(), 0, Some(1));
+            }
+            if matches!(state_, 4) {
+                p_.check_count("--parallel", analysis_stats__parallel.len(), 0, Some(1));
+                p_.check_count("<path>", analysis_stats__path.1.len(), 1, Some(1));
+            }
+            p_.finish_checks()?;
+            Ok(RustAnalyzer {
+                verbose: verbose.len() as u32,
+                subcommand: match state_ {
+                    2 | 3 => RustAnalyzerCmd::Server(Server {
+                        dir: server__dir.pop(),
+                        subcommand: match state_ {
+                            2 => ServerCmd::Launch(Launch { log: !server__launch__log.is_empty() }),
+                            3 => ServerCmd::Watch(Watch {}),
+                            _ => unreachable!(),
+                        },
+                    }),
+                    4 => RustAnalyzerCmd::AnalysisStats(AnalysisStats {
+                        parallel: !analysis_stats__parallel.is_empty(),
+                        path: analysis_stats__path.1.pop().unwrap(),
+                    }),
+                    _ => unreachable!(),
                 },
+            })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("rust-analyzer", "rust-analyzer [-v]... [-h] <COMMAND>", true),
+                1 => (
+                    "rust-analyzer server",
+                    "rust-analyzer server [--dir <path>] [--log] <COMMAND>",
+                    true,
+                ),
+                2 => ("rust-analyzer server launch", "rust-analyzer server launch [--log]", true),
+                3 => ("rust-analyzer server watch", "rust-analyzer server watch", true),
+                4 => (
+                    "rust-analyzer analysis-stats",
+                    "rust-analyzer analysis-stats <path> [--parallel]",
+                    false,
+                ),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
+    }
+}
+impl RustAnalyzer {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        for _ in 0..self.verbose {
+            w_.flag("--verbose");
+        }
+        match &self.subcommand {
+            RustAnalyzerCmd::Server(it) => {
+                w_.subcommand("server");
+                it.unparse_(w_);
+            }
+            RustAnalyzerCmd::AnalysisStats(it) => {
+                w_.subcommand("analysis-stats");
+                it.unparse_(w_);
             }
         }
-        state_ = if state_ == 1 { 2 } else { state_ };
-        Ok(RustAnalyzer {
-            verbose: verbose.len() as u32,
-            subcommand: match state_ {
-                2 | 3 => RustAnalyzerCmd::Server(Server {
-                    dir: p_.optional("--dir", server__dir)?,
-                    subcommand: match state_ {
-                        2 => ServerCmd::Launch(Launch {
-                            log: p_.optional("--log", server__launch__log)?.is_some(),
-                        }),
-                        3 => ServerCmd::Watch(Watch {}),
-                        _ => return Err(p_.subcommand_required()),
-                    },
-                }),
-                4 => RustAnalyzerCmd::AnalysisStats(AnalysisStats {
-                    parallel: p_.optional("--parallel", analysis_stats__parallel)?.is_some(),
-                    path: p_.required("path", analysis_stats__path.1)?,
-                }),
-                _ => return Err(p_.subcommand_required()),
-            },
-        })
+    }
+}
+impl Server {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if let Some(it) = &self.dir {
+            w_.flag("--dir");
+            w_.value(it);
+        }
+        match &self.subcommand {
+            ServerCmd::Launch(it) => {
+                it.unparse_(w_);
+            }
+            ServerCmd::Watch(it) => {
+                w_.subcommand("watch");
+                it.unparse_(w_);
+            }
+        }
+    }
+}
+impl Launch {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if self.log {
+            w_.flag("--log");
+        }
+    }
+}
+impl Watch {
+    fn unparse_(&self, _w: &mut xflags::rt::Unparser) {}
+}
+impl AnalysisStats {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if self.parallel {
+            w_.flag("--parallel");
+        }
+        w_.arg(&self.path, false);
     }
 }
 impl RustAnalyzer {
-    const HELP_SERVER__LAUNCH__: &'static str = "Usage: launch [--log]
+    const HELP_SERVER__LAUNCH__: &'static str = "Usage: rust-analyzer server launch [--log]
 Options:
   --log                
 
 Commands:
   help                 Print this message or the help of the given subcommand(s)";
-    const HELP_SERVER__WATCH__: &'static str = "Usage: watch
+    const HELP_SHORT_SERVER__LAUNCH__: &'static str = "Usage: rust-analyzer server launch [--log]
+Options:
+  --log                
+
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SERVER__WATCH__: &'static str = "Usage: rust-analyzer server watch
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SHORT_SERVER__WATCH__: &'static str = "Usage: rust-analyzer server watch
+Commands:
+  help                 Print this message or the help of the given subcommand(s)";
+    const HELP_SERVER__: &'static str =
+        "Usage: rust-analyzer server [--dir <path>] [--log] <COMMAND>
+Options:
+  --dir <path>         
+  --log                
+
 Commands:
+  watch                
   help                 Print this message or the help of the given subcommand(s)";
-    const HELP_SERVER__: &'static str = "Usage: server [--dir <path>] [--log] <COMMAND>
+    const HELP_SHORT_SERVER__: &'static str =
+        "Usage: rust-analyzer server [--dir <path>] [--log] <COMMAND>
 Options:
   --dir <path>         
   --log                
@@ -168,16 +359,30 @@ Options:
 Commands:
   watch                
   help                 Print this message or the help of the given subcommand(s)";
-    const HELP_ANALYSIS_STATS__: &'static str = "Usage: analysis-stats <path> [--parallel]
+    const HELP_ANALYSIS_STATS__: &'static str =
+        "Usage: rust-analyzer analysis-stats <path> [--parallel]
 Arguments:
   <path>               
 
 Options:
-  --parallel           
+  --parallel           ";
+    const HELP_SHORT_ANALYSIS_STATS__: &'static str =
+        "Usage: rust-analyzer analysis-stats <path> [--parallel]
+Arguments:
+  <path>               
+
+Options:
+  --parallel           ";
+    const HELP_: &'static str = "Usage: rust-analyzer [-v]... [-h] <COMMAND>
+Options:
+  -v, --verbose        
+  -h, --help           Prints help
 
 Commands:
+  server               
+  analysis-stats       
   help                 Print this message or the help of the given subcommand(s)";
-    const HELP_: &'static str = "Usage: rust-analyzer [-v]... [-h] <COMMAND>
+    const HELP_SHORT_: &'static str = "Usage: rust-analyzer [-v]... [-h] <COMMAND>
 Options:
   -v, --verbose        
   -h, --help           Prints help
@@ -186,4 +391,137 @@ Commands:
   server               
   analysis-stats       
   help                 Print this message or the help of the given subcommand(s)";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"rust-analyzer\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [],
+  \"flags\": [
+    {
+      \"long\": \"verbose\",
+      \"short\": \"v\",
+      \"arity\": \"repeated\",
+      \"min\": 0,
+      \"max\": null,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [
+    {
+      \"name\": \"server\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [],
+      \"flags\": [
+        {
+          \"long\": \"dir\",
+          \"short\": null,
+          \"arity\": \"optional\",
+          \"min\": 0,
+          \"max\": 1,
+          \"value\": {
+            \"name\": \"path\",
+            \"type\": \"PathBuf\"
+          },
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [
+        {
+          \"name\": \"launch\",
+          \"aliases\": [],
+          \"default\": true,
+          \"doc\": null,
+          \"section\": null,
+          \"args\": [],
+          \"flags\": [
+            {
+              \"long\": \"log\",
+              \"short\": null,
+              \"arity\": \"optional\",
+              \"min\": 0,
+              \"max\": 1,
+              \"value\": null,
+              \"delimiter\": null,
+              \"doc\": null,
+              \"section\": null
+            }
+          ],
+          \"subcommands\": [],
+          \"examples\": [],
+          \"after_help\": null
+        },
+        {
+          \"name\": \"watch\",
+          \"aliases\": [],
+          \"default\": false,
+          \"doc\": null,
+          \"section\": null,
+          \"args\": [],
+          \"flags\": [],
+          \"subcommands\": [],
+          \"examples\": [],
+          \"after_help\": null
+        }
+      ],
+      \"examples\": [],
+      \"after_help\": null
+    },
+    {
+      \"name\": \"analysis-stats\",
+      \"aliases\": [],
+      \"default\": false,
+      \"doc\": null,
+      \"section\": null,
+      \"args\": [
+        {
+          \"name\": \"path\",
+          \"arity\": \"required\",
+          \"min\": 1,
+          \"max\": 1,
+          \"type\": \"PathBuf\",
+          \"doc\": null
+        }
+      ],
+      \"flags\": [
+        {
+          \"long\": \"parallel\",
+          \"short\": null,
+          \"arity\": \"optional\",
+          \"min\": 0,
+          \"max\": 1,
+          \"value\": null,
+          \"delimiter\": null,
+          \"doc\": null,
+          \"section\": null
+        }
+      ],
+      \"subcommands\": [],
+      \"examples\": [],
+      \"after_help\": null
+    }
+  ],
+  \"examples\": [],
+  \"after_help\": null
+}";
 }
@@ -0,0 +1,287 @@
+#![allow(dead_code)] // unused fields
+#[allow(unused)]
+use std::{ffi::OsString, path::PathBuf};
+
+#[derive(Debug)]
+pub struct Trailing {
+    pub first: String,
+    pub sources: Vec<PathBuf>,
+    pub dest: PathBuf,
+    pub mode: u32,
+
+    pub recursive: bool,
+}
+
+impl Trailing {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_args<I>(args: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_vec_(args.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+        Self::from_args(args.iter().copied())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::ffi::OsString>,
+    {
+        Self::from_argv_(argv.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+        Self::from_command_line_(command_line)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec_partial(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        Self::from_vec_partial_(args)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_args(&self) -> Vec<std::ffi::OsString> {
+        self.to_args_()
+    }
+}
+
+impl Trailing {
+    fn from_env_or_exit_() -> Self {
+        Self::from_env_().unwrap_or_else(|err| err.exit())
+    }
+    fn from_env_() -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_env();
+        Self::parse_(&mut p)
+    }
+    fn from_vec_(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new(args);
+        Self::parse_(&mut p)
+    }
+    fn from_argv_(argv: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_argv(argv);
+        Self::parse_(&mut p)
+    }
+    fn from_command_line_(command_line: &str) -> xflags::Result<Self> {
+        let mut p = xflags::rt::Parser::new_from_command_line(command_line)?;
+        Self::parse_(&mut p)
+    }
+    fn from_vec_partial_(
+        args: Vec<std::ffi::OsString>,
+    ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+        let mut p = xflags::rt::Parser::new_partial(args);
+        let res = Self::parse_(&mut p)?;
+        Ok((res, p.into_rest()))
+    }
+    fn to_args_(&self) -> Vec<std::ffi::OsString> {
+        let mut w = xflags::rt::Unparser::default();
+        self.unparse_(&mut w);
+        w.finish()
+    }
+}
+
+impl Trailing {
+    fn parse_(p_: &mut xflags::rt::Parser) -> xflags::Result<Self> {
+        #![allow(non_snake_case, unused_mut)]
+        p_.set_command_name("trailing");
+        let mut recursive = Vec::new();
+        let mut first = (false, Vec::new());
+        let mut sources = (false, Vec::new());
+        let mut dest = (false, Vec::new());
+        let mut mode = (false, Vec::new());
+        let mut rest_ = Vec::new();
+
+        let mut state_ = 0u8;
+        let mut help_ = false;
+        let mut parse_ = || -> xflags::Result<Self> {
+            while let Some(arg_) = p_.pop_flag() {
+                match arg_ {
+                    Ok(flag_) => match (state_, flag_.as_str()) {
+                        (0, "--help") => return Err(p_.help(Self::HELP_)),
+                        (0, "-h") => return Err(p_.help(Self::HELP_SHORT_)),
+                        (0, "--recursive" | "-r") => recursive.push(()),
+                        _ => p_.unexpected_flag(flag_)?,
+                    },
+                    Err(arg_) => match (state_, arg_.to_str().unwrap_or("")) {
+                        (0, _) => {
+                            if let (done_ @ false, buf_) = &mut first {
+                                buf_.push(p_.value_from_str::<String>("first", "String", arg_)?);
+                                *done_ = true;
+                                continue;
+                            }
+                            rest_.push((p_.position(), arg_));
+                        }
+                        _ => p_.unexpected_arg(arg_)?,
+                    },
+                }
+            }
+            if help_ {
+                let help_ = match state_ {
+                    0 => Self::HELP_,
+                    _ => unreachable!(),
+                };
+                return Err(p_.help(help_));
+            }
+            let tail_ = rest_.split_off(rest_.len().saturating_sub(2));
+            for (position_, arg_) in rest_ {
+                p_.set_position(position_);
+                sources.1.push(arg_.into());
+            }
+            let mut tail_ = tail_.into_iter();
+            if let Some((position_, arg_)) = tail_.next() {
+                p_.set_position(position_);
+                dest.1.push(arg_.into());
+            }
+            if let Some((position_, arg_)) = tail_.next() {
+                p_.set_position(position_);
+                mode.1.push(p_.value_from_str::<u32>("mode", "u32", arg_)?);
+            }
+            p_.check_count("--recursive", recursive.len(), 0, Some(1));
+            p_.check_count("<first>", first.1.len(), 1, Some(1));
+            p_.check_count("<sources>...", sources.1.len(), 1, None);
+            p_.check_count("<dest>", dest.1.len(), 1, Some(1));
+            p_.check_count("<mode>", mode.1.len(), 1, Some(1));
+            p_.finish_checks()?;
+            Ok(Trailing {
+                recursive: !recursive.is_empty(),
+                first: first.1.pop().unwrap(),
+                sources: sources.1,
+                dest: dest.1.pop().unwrap(),
+                mode: mode.1.pop().unwrap(),
+            })
+        };
+        parse_().map_err(|err_| {
+            let (command_, usage_, help_command_) = match state_ {
+                0 => ("trailing", "trailing <first> <sources>... <dest> <mode> [-r] [-h]", false),
+                _ => unreachable!(),
+            };
+            p_.with_usage(err_, command_, usage_, help_command_)
+        })
+    }
+}
+impl Trailing {
+    fn unparse_(&self, w_: &mut xflags::rt::Unparser) {
+        if self.recursive {
+            w_.flag("--recursive");
+        }
+        w_.arg(&self.first, false);
+        for it in &self.sources {
+            w_.arg(it, false);
+        }
+        w_.arg(&self.dest, false);
+        w_.arg(self.mode.to_string(), false);
+    }
+}
+impl Trailing {
+    const HELP_: &'static str = "Usage: trailing <first> <sources>... <dest> <mode> [-r] [-h]
+Arguments:
+  <first>              
+  <sources>...         
+  <dest>               
+  <mode>               
+
+Options:
+  -r, --recursive      
+  -h, --help           Prints help";
+    const HELP_SHORT_: &'static str = "Usage: trailing <first> <sources>... <dest> <mode> [-r] [-h]
+Arguments:
+  <first>              
+  <sources>...         
+  <dest>               
+  <mode>               
+
+Options:
+  -r, --recursive      
+  -h, --help           Prints help";
+    pub const SPEC_JSON: &'static str = "{
+  \"name\": \"trailing\",
+  \"aliases\": [],
+  \"default\": false,
+  \"doc\": null,
+  \"section\": null,
+  \"args\": [
+    {
+      \"name\": \"first\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"type\": \"String\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"sources\",
+      \"arity\": \"repeated\",
+      \"min\": 1,
+      \"max\": null,
+      \"type\": \"PathBuf\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"dest\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"type\": \"PathBuf\",
+      \"doc\": null
+    },
+    {
+      \"name\": \"mode\",
+      \"arity\": \"required\",
+      \"min\": 1,
+      \"max\": 1,
+      \"type\": \"u32\",
+      \"doc\": null
+    }
+  ],
+  \"flags\": [
+    {
+      \"long\": \"recursive\",
+      \"short\": \"r\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": null,
+      \"section\": null
+    },
+    {
+      \"long\": \"help\",
+      \"short\": \"h\",
+      \"arity\": \"optional\",
+      \"min\": 0,
+      \"max\": 1,
+      \"value\": null,
+      \"delimiter\": null,
+      \"doc\": \"Prints help\",
+      \"section\": null
+    }
+  ],
+  \"subcommands\": [],
+  \"examples\": [],
+  \"after_help\": null
+}";
+}
@@ -17,14 +17,22 @@ mod flags {
     // generated start
     // The following code is generated by `xflags` macro.
     // Run `env UPDATE_XFLAGS=1 cargo build` to regenerate.
+    /// Prints a greeting.
     #[derive(Debug)]
     pub struct Hello {
+        /// Whom to greet.
         pub name: String,
 
+        /// Use non-ascii symbols in the output.
         pub emoji: bool,
     }
 
     impl Hello {
+        #[allow(dead_code)]
+        pub fn from_env_or_exit() -> Self {
+            Self::from_env_or_exit_()
+        }
+
         #[allow(dead_code)]
         pub fn from_env() -> xflags::Result<Self> {
             Self::from_env_()
@@ -34,6 +42,41 @@ mod flags {
         pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
             Self::from_vec_(args)
         }
+
+        #[allow(dead_code)]
+        pub fn from_args<I>(args: I) -> xflags::Result<Self>
+        where
+            I: IntoIterator,
+            I::Item: Into<std::ffi::OsString>,
+        {
+            Self::from_vec_(args.into_iter().map(Into::into).collect())
+        }
+
+        #[allow(dead_code)]
+        pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+            Self::from_args(args.iter().copied())
+        }
+
+        #[allow(dead_code)]
+        pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+        where
+            I: IntoIterator,
+            I::Item: Into<std::ffi::OsString>,
+        {
+            Self::from_argv_(argv.into_iter().map(Into::into).collect())
+        }
+
+        #[allow(dead_code)]
+        pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+            Self::from_command_line_(command_line)
+        }
+
+        #[allow(dead_code)]
+        pub fn from_vec_partial(
+            args: Vec<std::ffi::OsString>,
+        ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+            Self::from_vec_partial_(args)
+        }
     }
     // generated end
 }
@@ -40,7 +40,9 @@ mod flags {
     // Run `env UPDATE_XFLAGS=1 cargo build` to regenerate.
     #[derive(Debug)]
     pub struct RustAnalyzer {
+        /// Set verbosity level
         pub verbose: u32,
+        /// Log to the specified file instead of stderr.
         pub log_file: Option<PathBuf>,
         pub subcommand: RustAnalyzerCmd,
     }
@@ -48,29 +50,43 @@ mod flags {
     #[derive(Debug)]
     pub enum RustAnalyzerCmd {
         RunServer(RunServer),
+        /// Parse tree
         Parse(Parse),
+        /// Benchmark specific analysis operation
         AnalysisBench(AnalysisBench),
     }
 
     #[derive(Debug)]
     pub struct RunServer {
+        /// Print version
         pub version: bool,
     }
 
+    /// Parse tree
     #[derive(Debug)]
     pub struct Parse {
+        /// Suppress printing
         pub no_dump: bool,
     }
 
+    /// Benchmark specific analysis operation
     #[derive(Debug)]
     pub struct AnalysisBench {
+        /// Directory with Cargo.toml
         pub path: Option<PathBuf>,
 
+        /// Compute syntax highlighting for this file
         pub highlight: PathBuf,
+        /// Compute highlighting for this line
         pub line: Option<u32>,
     }
 
     impl RustAnalyzer {
+        #[allow(dead_code)]
+        pub fn from_env_or_exit() -> Self {
+            Self::from_env_or_exit_()
+        }
+
         #[allow(dead_code)]
         pub fn from_env() -> xflags::Result<Self> {
             Self::from_env_()
@@ -80,6 +96,41 @@ mod flags {
         pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
             Self::from_vec_(args)
         }
+
+        #[allow(dead_code)]
+        pub fn from_args<I>(args: I) -> xflags::Result<Self>
+        where
+            I: IntoIterator,
+            I::Item: Into<std::ffi::OsString>,
+        {
+            Self::from_vec_(args.into_iter().map(Into::into).collect())
+        }
+
+        #[allow(dead_code)]
+        pub fn from_str_args(args: &[&str]) -> xflags::Result<Self> {
+            Self::from_args(args.iter().copied())
+        }
+
+        #[allow(dead_code)]
+        pub fn from_argv<I>(argv: I) -> xflags::Result<Self>
+        where
+            I: IntoIterator,
+            I::Item: Into<std::ffi::OsString>,
+        {
+            Self::from_argv_(argv.into_iter().map(Into::into).collect())
+        }
+
+        #[allow(dead_code)]
+        pub fn from_command_line(command_line: &str) -> xflags::Result<Self> {
+            Self::from_command_line_(command_line)
+        }
+
+        #[allow(dead_code)]
+        pub fn from_vec_partial(
+            args: Vec<std::ffi::OsString>,
+        ) -> xflags::Result<(Self, Vec<std::ffi::OsString>)> {
+            Self::from_vec_partial_(args)
+        }
     }
     // generated end
 }
@@ -0,0 +1,27 @@
+mod flags {
+    xflags::xflags! {
+        /// Service administration shell.
+        cmd admin {
+            /// Show status of all services.
+            cmd status {}
+            /// Restart a service.
+            cmd restart {
+                required service: String
+                optional -f, --force
+            }
+        }
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let mut repl = xflags::Repl::new("admin> ");
+    while let Some(cmd) = repl.next(flags::Admin::from_command_line) {
+        match cmd?.subcommand {
+            flags::AdminCmd::Status(_) => println!("all services are running"),
+            flags::AdminCmd::Restart(it) => {
+                println!("restarting {}{}", it.service, if it.force { " (forced)" } else { "" })
+            }
+        }
+    }
+    Ok(())
+}
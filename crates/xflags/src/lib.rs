@@ -51,9 +51,6 @@
 //! Options:
 //!   -r, --recursive      Remove directories and their contents recursively.
 //!   -h, --help           Prints help
-//!
-//! Commands:
-//!   help                 Print this message or the help of the given subcommand(s)
 //! ```
 //!
 //! For larger programs, you'd typically want to use `xflags!` macro, which
@@ -108,6 +105,39 @@
 //!
 //! xflags correctly handles non-utf8 arguments.
 //!
+//! With the **to_args** keyword at the top of `xflags!`, a `to_args` method is
+//! generated as well. It turns parsed flags back into a canonical list of
+//! arguments, which parses to the same value. This is handy for re-executing
+//! the current binary or logging a reproducible invocation. Switches come
+//! before positional arguments of each command, and `--` is inserted before
+//! the first of them that looks like a switch or reads `help` where `help` is
+//! a command. The only value that can't be
+//! written out this way is such an argument followed by switches of a
+//! default subcommand, which has no name to end the `--`. Values of `FromStr`
+//! types are written out with `Display`, so they must implement it.
+//!
+//! Besides `from_env` and `from_vec`, generated parsers can be created with
+//! `from_args`, which accepts any iterator of strings, `from_str_args`, which
+//! is convenient in tests, and `from_argv`, which expects the program name as
+//! the first argument. `from_command_line` splits a single string into
+//! arguments following POSIX shell quoting rules (single and double quotes,
+//! backslash escapes) and then parses them; the string should not include the
+//! program name. [`Repl`] builds an interactive shell on top of it: it reads
+//! lines from standard input, parses each one, and prints help and errors to
+//! standard output without exiting the process. See `examples/repl.rs`.
+//!
+//! The generated `from_vec_partial` method parses the arguments it
+//! recognizes and returns the rest, in order, instead of failing on unknown
+//! switches and arguments. This is useful for front-ends which forward
+//! everything they don't understand to an inner tool. Note that the value of
+//! an unknown switch might be taken for a positional argument.
+//!
+//! The generated type also has a `SPEC_JSON` constant with a JSON description
+//! of the whole command tree: commands with their aliases and docs,
+//! positional arguments, and switches with their short and long names, arity,
+//! value names and types. It is meant for tools like documentation and shell
+//! completion generators.
+//!
 //! ## Syntax Reference
 //!
 //! The `xflags!` macro uses **cmd** keyword to introduce a command or
@@ -149,6 +179,20 @@
 //! }
 //! ```
 //!
+//! Optional and repeated switches with values accept a **delimiter**, which
+//! splits each value into several elements: `--features a,b --features c`
+//! produces `vec!["a", "b", "c"]`. A backslash before the delimiter keeps it
+//! in the element, `a\,b` is a single `a,b`. Other backslashes are literal, so
+//! Windows paths need no escaping.
+//!
+//! ```
+//! xflags::xflags! {
+//!     cmd delimited-values {
+//!         repeated -F, --features feature: String delimiter ','
+//!     }
+//! }
+//! ```
+//!
 //! Arguments without `--` in then are are positional.
 //!
 //! ```
@@ -162,6 +206,39 @@
 //! }
 //! ```
 //!
+//! Positional arguments are filled in order, so a required argument can't
+//! follow an optional one. Only required arguments can follow a repeated one,
+//! and they take the last values, like in `cp SRC... DEST`.
+//!
+//! ```
+//! use std::path::PathBuf;
+//!
+//! xflags::xflags! {
+//!     cmd cp {
+//!         repeated{1..} sources: PathBuf
+//!         required dest: PathBuf
+//!     }
+//! }
+//! ```
+//!
+//! Repeated switches and arguments can limit the number of occurrences with a
+//! range in curly braces: `{1..}` is "one or more", `{..=3}` is "at most
+//! three", and `{2}` is "exactly two". For switches with a **delimiter**, the
+//! range bounds how many times the switch is given, not the number of values
+//! after splitting: `repeated{..=1} --targets t: String delimiter ','` accepts
+//! `--targets a,b` but rejects `--targets a --targets b`.
+//!
+//! ```
+//! use std::path::PathBuf;
+//!
+//! xflags::xflags! {
+//!     cmd bounded {
+//!         repeated{..=3} -v, --verbose
+//!         repeated{1..} files: PathBuf
+//!     }
+//! }
+//! ```
+//!
 //! You can create aliases if desired, which is as simple as adding extra names to the `cmd` definition.
 //! In this case, `run` can be called as `run`, `r` and `exec`:
 //!
@@ -223,12 +300,16 @@
 //! ```
 //!
 //! Switches are always "inherited". Both `app -v foo` and `app foo -v` produce
-//! the same result.
+//! the same result. `--` makes the following arguments positional only up to
+//! the name of a subcommand: `app -- -x foo -v` passes `-x` to `app` and still
+//! parses `-v` as a switch.
 //!
 //! To make subcommand name optional use the **default** keyword to mark a
 //! subcommand to select if no subcommand name is passed. The name of the
 //! default subcommand affects only the name of the generated Rust struct, it
-//! can't be specified explicitly on the command line.
+//! can't be specified explicitly on the command line. Positional arguments
+//! are filled in order, first those of the command and then those of its
+//! default subcommand, even if switches of the subcommand come before them.
 //!
 //! ```
 //! xflags::xflags! {
@@ -241,7 +322,11 @@
 //! ```
 //!
 //! Commands, arguments, and switches can be documented. Doc comments become a
-//! part of generated help:
+//! part of generated help. `--help` and `help` show doc comments in full,
+//! while `-h` shows a compact summary with only the first paragraph of each.
+//! `help` is recognized where a subcommand is expected, and `help <commands>`
+//! shows the help of a subcommand. Leaf commands with positional arguments
+//! take `help` as an argument, so there only `--help` works:
 //!
 //! ```
 //! mod flags {
@@ -270,6 +355,111 @@
 //! # fn run_checks(_config: Option<std::path::PathBuf>, _verbosity: u32) {}
 //! ```
 //!
+//! The **section** keyword groups switches and subcommands under a custom
+//! heading in help and in the Markdown reference. Switches and subcommands
+//! from sections with the same title share the heading. Sections don't affect
+//! the generated structs.
+//!
+//! ```
+//! xflags::xflags! {
+//!     cmd app {
+//!         optional -v, --verbose
+//!         section "Network options" {
+//!             optional --proxy url: String
+//!             optional --offline
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The **example** keyword adds a usage example, with an optional
+//! description, to the help of a command, and **after_help** adds free-form
+//! text at the end of it. Both are also included in the JSON and Markdown
+//! descriptions.
+//!
+//! ```
+//! xflags::xflags! {
+//!     cmd app {
+//!         optional --release
+//!
+//!         example "app --release" "Build optimized binaries"
+//!         after_help "See the handbook for more details."
+//!     }
+//! }
+//! ```
+//!
+//! Generated structs and enums derive `Debug`. The **derive** keyword at the
+//! top of the macro replaces the list of derived traits for all generated
+//! types. A `#[derive(...)]` attribute on a command overrides the list for that
+//! command's struct and subcommand enum. Other outer attributes on commands,
+//! switches, and arguments are copied to the corresponding generated items and
+//! fields. Attributes of the subcommand enum go before the **enum** keyword in
+//! the command's body. If `Default` is derived, the enum of subcommands
+//! defaults to the **default** subcommand, or to the first one.
+//!
+//! ```
+//! xflags::xflags! {
+//!     derive(Debug, Clone, PartialEq, Eq)
+//!
+//!     #[non_exhaustive]
+//!     cmd app {
+//!         repeated -v, --verbose
+//!         #[non_exhaustive]
+//!         enum
+//!         #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+//!         cmd foo {}
+//!     }
+//! }
+//! ```
+//!
+//! Generated types and fields are `pub` by default. A visibility before the
+//! top-level `cmd`, like `pub(crate)`, applies to all of them. The
+//! **subcommand_field** and **enum_suffix** keywords rename the field holding
+//! the subcommand (`subcommand` by default) and the suffix of the subcommand
+//! enums (`Cmd` by default). Use `as` to give a switch or an argument a field
+//! name different from its spelling on the command line:
+//!
+//! ```
+//! # use std::path::PathBuf;
+//! xflags::xflags! {
+//!     subcommand_field command
+//!     enum_suffix Command
+//!
+//!     pub(crate) cmd app {
+//!         optional --type as kind ty: String
+//!         required path as target: PathBuf
+//!         cmd build {}
+//!     }
+//! }
+//!
+//! # fn f(flags: App) -> Option<String> {
+//! let AppCommand::Build(Build) = flags.command;
+//! # let _ = flags.target;
+//! flags.kind
+//! # }
+//! ```
+//!
+//! Usage lines in help and errors start with the name of the program from
+//! `argv[0]`, or with the name of the top-level command if it is not known,
+//! followed by the path of subcommands. The **program** keyword sets the name
+//! explicitly:
+//!
+//! ```
+//! xflags::xflags! {
+//!     program "ra"
+//!
+//!     cmd rust-analyzer {
+//!         cmd server {}
+//!     }
+//! }
+//!
+//! let err = RustAnalyzer::from_str_args(&["server", "--help"]).unwrap_err();
+//! assert!(err.to_string().starts_with("Usage: ra server"));
+//! ```
+//!
+//! Keywords at the top of the macro, before the command, can come in any
+//! order, but each of them at most once.
+//!
 //! The **src** keyword controls how the code generation works. If it is absent,
 //! `xflags` acts as a typical procedure macro, which generates a bunch of
 //! structs and impls.
@@ -280,6 +470,14 @@
 //! the `UPDATE_XFLAGS` environmental variable is set, the macro will write them
 //! directly to the specified file.
 //!
+//! Similarly, the **markdown** keyword specifies a Markdown file to keep a CLI
+//! reference in. When `UPDATE_XFLAGS` is set, the text between
+//! `<!-- xflags start -->` and `<!-- xflags end -->` markers is replaced with a
+//! section per command, listing its arguments, switches, and subcommands. If
+//! there are no markers, the reference is appended to the end of the file. A
+//! start marker without an end marker after it, or an end marker before the
+//! start marker, is an error.
+//!
 //! By convention, `xflag!` macro should be invoked from the `flags` submodule.
 //! The `flags::` prefix should be used to refer to command names. Additional
 //! validation logic can go to the `flags` module:
@@ -309,7 +507,15 @@
 //! The `parse_or_exit!` macro is a syntactic sure for `xflags!`, which
 //! immediately parses the argument, exiting the process if needed.
 //! `parse_or_exit` only supports single top-level command and doesn't need the
-//! `cmd`  keyword.
+//! `cmd`  keyword. Of the keywords at the top, it only accepts **program**:
+//!
+//! ```no_run
+//! let flags = xflags::parse_or_exit! {
+//!     program "rm"
+//!
+//!     optional -r, --recursive
+//! };
+//! ```
 //!
 //! ## Limitations
 //!
@@ -332,6 +538,8 @@ use std::fmt;
 /// See the module-level for detailed syntax specification.
 pub use xflags_macros::{parse_or_exit, xflags};
 
+pub use crate::repl::Repl;
+
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// An error occurred when parssing command line arguments.
@@ -342,6 +550,10 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub struct Error {
     msg: String,
     help: bool,
+    /// Whether the message should point at the help, see `rt::Parser::with_usage`.
+    hint: bool,
+    command: Option<String>,
+    usage: Option<String>,
 }
 
 impl fmt::Display for Error {
@@ -357,7 +569,7 @@ impl Error {
     ///
     /// Use this to report custom validation errors.
     pub fn new(message: impl Into<String>) -> Error {
-        Error { msg: message.into(), help: false }
+        Error { msg: message.into(), help: false, hint: false, command: None, usage: None }
     }
 
     /// Error that carries `--help` message.
@@ -365,13 +577,28 @@ impl Error {
         self.help
     }
 
+    /// Path of the subcommand which was being parsed, like `rust-analyzer
+    /// analysis-stats`.
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+
+    /// Usage line of the subcommand which was being parsed, like
+    /// `rust-analyzer analysis-stats <path> [--parallel]`.
+    pub fn usage(&self) -> Option<&str> {
+        self.usage.as_deref()
+    }
+
     /// Prints the error and exists the process.
     pub fn exit(self) -> ! {
         if self.is_help() {
             println!("{self}");
             std::process::exit(0)
         } else {
-            eprintln!("{self}");
+            eprintln!("error: {self}");
+            if let Some(usage) = &self.usage {
+                eprintln!("\nUsage: {usage}");
+            }
             std::process::exit(2)
         }
     }
@@ -386,3 +613,5 @@ impl Error {
 /// Private impl details for macros.
 #[doc(hidden)]
 pub mod rt;
+mod repl;
+mod shell;
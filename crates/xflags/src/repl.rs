@@ -0,0 +1,83 @@
+use std::io::{self, BufRead, StdinLock, Stdout, Write};
+
+use crate::Result;
+
+/// Interactive read-parse loop over a command tree.
+///
+/// Each line is split like a shell command and handed to a parser, usually
+/// the generated `from_command_line`. Help and errors are printed, and the
+/// loop goes on to the next line instead of exiting the process.
+///
+/// The prompt, help and parse errors all go to the REPL's output, as they are
+/// a part of the conversation with the user. Only the parsed commands are
+/// returned, what the program prints for them is up to it.
+///
+/// ```no_run
+/// xflags::xflags! {
+///     cmd admin {
+///         cmd status {}
+///         cmd restart { required service: String }
+///     }
+/// }
+///
+/// let mut repl = xflags::Repl::new("admin> ");
+/// while let Some(cmd) = repl.next(Admin::from_command_line) {
+///     match cmd?.subcommand {
+///         AdminCmd::Status(_) => println!("ok"),
+///         AdminCmd::Restart(it) => println!("restarting {}", it.service),
+///     }
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Repl<R = StdinLock<'static>, W = Stdout> {
+    input: R,
+    output: W,
+    prompt: String,
+}
+
+impl Repl {
+    /// Creates a REPL over standard input and output.
+    pub fn new(prompt: impl Into<String>) -> Repl {
+        Repl::with_io(io::stdin().lock(), io::stdout(), prompt)
+    }
+}
+
+impl<R: BufRead, W: Write> Repl<R, W> {
+    /// Creates a REPL over the given input and output.
+    pub fn with_io(input: R, output: W, prompt: impl Into<String>) -> Repl<R, W> {
+        Repl { input, output, prompt: prompt.into() }
+    }
+
+    /// Reads lines until one of them parses successfully.
+    ///
+    /// Returns `None` at the end of input, and an error if reading the input
+    /// or writing the output fails.
+    pub fn next<T>(&mut self, parse: impl FnMut(&str) -> Result<T>) -> Option<io::Result<T>> {
+        self.next_(parse).transpose()
+    }
+
+    fn next_<T>(&mut self, mut parse: impl FnMut(&str) -> Result<T>) -> io::Result<Option<T>> {
+        let mut line = String::new();
+        loop {
+            write!(self.output, "{}", self.prompt)?;
+            self.output.flush()?;
+
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse(&line) {
+                Ok(it) => return Ok(Some(it)),
+                Err(err) => writeln!(self.output, "{err}")?,
+            }
+        }
+    }
+
+    /// Returns the underlying output, for example to inspect it in tests.
+    pub fn into_output(self) -> W {
+        self.output
+    }
+}
@@ -1,10 +1,15 @@
-use std::{ffi::OsString, fmt, str::FromStr};
+use std::{
+    ffi::{OsStr, OsString},
+    fmt, iter, mem,
+    path::Path,
+    str::FromStr,
+};
 
 use crate::{Error, Result};
 
 macro_rules! format_err {
     ($($tt:tt)*) => {
-        Error { msg: format!($($tt)*), help: false }
+        Error::new(format!($($tt)*))
     };
 }
 
@@ -15,31 +20,69 @@ macro_rules! bail {
 }
 
 pub struct Parser {
+    progn: Option<OsString>,
     after_double_dash: bool,
     rargs: Vec<OsString>,
+    /// Position of the last taken argument in argv, for error messages.
+    position: usize,
+    /// Unrecognized arguments, collected instead of failing in partial mode.
+    rest: Option<Vec<OsString>>,
+    /// Missing and duplicate items, reported together by `finish_checks`.
+    problems: Vec<String>,
+    missing: bool,
+    /// Name of the top-level command in the DSL, replaced with the program
+    /// name from `argv[0]` in usage lines.
+    command_name: Option<&'static str>,
 }
 
 impl Parser {
     pub fn new(mut args: Vec<OsString>) -> Self {
-        // parse `help` command last when encountered somewhere along the way to be able to do
-        // `help <commands>` or `cmd help sub` without creating a bunch of leafs in the parse tree for it
-        if let Some((i, _)) = args.iter().enumerate().find(|(_, arg)| *arg == "help") {
-            args.remove(i);
-            args.push("--help".into())
+        args.reverse();
+
+        Self {
+            progn: None,
+            after_double_dash: false,
+            rargs: args,
+            position: 0,
+            rest: None,
+            problems: Vec::new(),
+            missing: false,
+            command_name: None,
         }
+    }
 
-        args.reverse();
+    pub fn new_partial(args: Vec<OsString>) -> Self {
+        let mut res = Parser::new(args);
+        res.rest = Some(Vec::new());
+        res
+    }
 
-        Self { after_double_dash: false, rargs: args }
+    pub fn into_rest(self) -> Vec<OsString> {
+        self.rest.unwrap_or_default()
     }
 
     pub fn new_from_env() -> Self {
         let args = std::env::args_os().collect::<Vec<_>>();
+        Parser::new_from_argv(args)
+    }
+
+    pub fn new_from_command_line(command_line: &str) -> Result<Self> {
+        let args = crate::shell::split(command_line)?;
+        Ok(Parser::new(args))
+    }
+
+    /// Like [`Parser::new`], but the first argument is the program name.
+    pub fn new_from_argv(mut args: Vec<OsString>) -> Self {
+        let progn = if args.is_empty() { None } else { Some(args.remove(0)) };
         let mut res = Parser::new(args);
-        let _progn = res.next();
+        res.progn = progn;
         res
     }
 
+    pub fn progn(&self) -> Option<&OsStr> {
+        self.progn.as_deref()
+    }
+
     pub fn pop_flag(&mut self) -> Option<Result<String, OsString>> {
         if self.after_double_dash {
             self.next().map(Err)
@@ -58,76 +101,366 @@ impl Parser {
         }
     }
 
+    pub fn set_command_name(&mut self, name: &'static str) {
+        self.command_name = Some(name);
+    }
+
+    /// Replaces the command name at the start of `text` with the program name.
+    fn with_program_name(&self, text: &str) -> String {
+        let (Some(name), Some(progn)) = (self.command_name, &self.progn) else {
+            return text.to_string();
+        };
+        let progn = Path::new(progn).file_name().unwrap_or(progn);
+        match text.strip_prefix(name) {
+            Some(rest) => format!("{}{rest}", progn.to_string_lossy()),
+            None => text.to_string(),
+        }
+    }
+
+    pub fn after_double_dash(&self) -> bool {
+        self.after_double_dash
+    }
+
+    /// `--` ends switches of the command it is passed to, a subcommand named
+    /// after it parses switches again.
+    pub fn enter_subcommand(&mut self) {
+        self.after_double_dash = false;
+    }
+
     pub fn push_back(&mut self, arg: Result<String, OsString>) {
         let arg = match arg {
             Ok(it) => it.into(),
             Err(it) => it,
         };
+        self.position -= 1;
         self.rargs.push(arg)
     }
 
     fn next(&mut self) -> Option<OsString> {
-        self.rargs.pop()
+        let res = self.rargs.pop()?;
+        self.position += 1;
+        Some(res)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Sets the position for errors about an argument which was taken earlier.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
     }
 
     pub fn next_value(&mut self, flag: &str) -> Result<OsString> {
         self.next().ok_or_else(|| format_err!("expected a value for `{flag}`"))
     }
 
-    pub fn next_value_from_str<T: FromStr>(&mut self, flag: &str) -> Result<T>
+    pub fn next_value_from_str<T: FromStr>(&mut self, flag: &str, ty: &str) -> Result<T>
     where
         T::Err: fmt::Display,
     {
         let value = self.next_value(flag)?;
-        self.value_from_str(flag, value)
+        self.value_from_str(flag, ty, value)
     }
 
-    pub fn value_from_str<T: FromStr>(&mut self, flag: &str, value: OsString) -> Result<T>
+    pub fn next_values<T: From<OsString>>(
+        &mut self,
+        flag: &str,
+        delimiter: char,
+    ) -> Result<Vec<T>> {
+        let value = self.next_value(flag)?;
+        Ok(split_value(value, delimiter).into_iter().map(T::from).collect())
+    }
+
+    pub fn next_values_from_str<T: FromStr>(
+        &mut self,
+        flag: &str,
+        ty: &str,
+        delimiter: char,
+    ) -> Result<Vec<T>>
     where
         T::Err: fmt::Display,
     {
-        match value.into_string() {
-            Ok(str) => str.parse::<T>().map_err(|err| format_err!("Can't parse `{flag}`, {err}")),
-            Err(it) => {
-                bail!("Can't parse `{flag}`, invalid utf8: {it:?}")
+        let value = self.next_value(flag)?;
+        split_value(value, delimiter)
+            .into_iter()
+            .map(|it| self.value_from_str(flag, ty, it))
+            .collect()
+    }
+
+    /// Parses `value` of `flag`, which is the name of a switch or an argument,
+    /// into `T`, named `ty` in the DSL.
+    pub fn value_from_str<T: FromStr>(&mut self, flag: &str, ty: &str, value: OsString) -> Result<T>
+    where
+        T::Err: fmt::Display,
+    {
+        let reason = match value.to_str() {
+            Some(str) => match str.parse::<T>() {
+                Ok(it) => return Ok(it),
+                Err(err) => int_error(ty, str).unwrap_or_else(|| format!("expected `{ty}`, {err}")),
+            },
+            None => format!("expected `{ty}`, invalid UTF-8"),
+        };
+        let (value, position) = (escape(&value), self.position);
+        bail!("Invalid value `{value}` for `{flag}` at argument {position}: {reason}")
+    }
+
+    pub fn unexpected_flag(&mut self, flag: String) -> Result<()> {
+        if let Some(rest) = &mut self.rest {
+            rest.push(flag.into());
+            return Ok(());
+        }
+        Err(Error { hint: true, ..format_err!("Unknown flag: `{flag}`") })
+    }
+
+    pub fn unexpected_arg(&mut self, arg: OsString) -> Result<()> {
+        if let Some(rest) = &mut self.rest {
+            // Keep `--` so that the forwarded arguments are not taken for flags.
+            if self.after_double_dash && !rest.iter().any(|it| it == "--") {
+                rest.push("--".into());
             }
+            rest.push(arg);
+            return Ok(());
         }
+
+        let arg = escape(&arg);
+        Err(Error { hint: true, ..format_err!("Unknown command: `{arg}`") })
     }
 
-    pub fn unexpected_flag(&self, flag: &str) -> Error {
-        format_err!("Unknown flag: `{flag}`. Use `help` for more information")
+    pub fn subcommand_required(&mut self) {
+        self.missing = true;
+        self.problems.push("A subcommand is required".to_string());
     }
 
-    pub fn unexpected_arg(&self, arg: OsString) -> Error {
-        // `to_string_lossy()` seems appropriate here but OsString's debug implementation actually
-        // escapes codes that are not valid utf-8, rather than replace them with `FFFD`
-        let dbg = format!("{arg:?}");
-        let arg = dbg.trim_matches('"');
+    pub fn help(&self, help: &'static str) -> Error {
+        let help = match help.strip_prefix("Usage: ") {
+            Some(rest) => format!("Usage: {}", self.with_program_name(rest)),
+            None => help.to_string(),
+        };
+        Error { help: true, ..Error::new(help) }
+    }
 
-        format_err!("Unknown command: `{arg}`. Use `help` for more information")
+    /// Adds the command being parsed to `err`. If the error is about the
+    /// command line as a whole, it also points at `help`, where the command
+    /// accepts it, or at `--help`.
+    pub fn with_usage(
+        &self,
+        mut err: Error,
+        command: &'static str,
+        usage: &'static str,
+        help_command: bool,
+    ) -> Error {
+        if err.hint {
+            err.hint = false;
+            err.msg.push_str(if err.msg.contains('\n') { "\n" } else { ". " });
+            let help = if help_command { "help" } else { "--help" };
+            err.msg.push_str(&format!("Use `{help}` for more information"));
+        }
+        if !err.help {
+            err.command = Some(self.with_program_name(command));
+            err.usage = Some(self.with_program_name(usage));
+        }
+        err
     }
 
-    pub fn subcommand_required(&self) -> Error {
-        format_err!("A subcommand is required. Use `help` for more information")
+    pub fn check_count(&mut self, usage: &str, count: usize, min: usize, max: Option<usize>) {
+        let kind = if usage.starts_with('-') { "Flag" } else { "Argument" };
+        let problem = match max {
+            Some(1) if count > 1 => format!("{kind} specified more than once: `{usage}`"),
+            Some(max) if count > max => {
+                format!("{kind} specified more than {max} times: `{usage}`")
+            }
+            _ if count >= min => return,
+            _ if min == 1 => format!("{kind} is required: `{usage}`"),
+            _ => format!("{kind} must be specified at least {min} times: `{usage}`"),
+        };
+        self.missing |= count < min;
+        self.problems.push(problem);
     }
 
-    pub fn help(&self, help: &'static str) -> Error {
-        Error { msg: help.to_string(), help: true }
+    pub fn finish_checks(&mut self) -> Result<()> {
+        let problems = std::mem::take(&mut self.problems);
+        let msg = match problems.as_slice() {
+            [] => return Ok(()),
+            [problem] => problem.clone(),
+            _ => {
+                let mut msg = String::from("Invalid arguments:");
+                for problem in &problems {
+                    msg.push_str("\n  ");
+                    msg.push_str(problem);
+                }
+                msg
+            }
+        };
+        Err(Error { hint: self.missing, ..format_err!("{msg}") })
     }
+}
+
+fn escape(value: &OsStr) -> String {
+    // `to_string_lossy()` seems appropriate here but OsString's debug implementation actually
+    // escapes codes that are not valid utf-8, rather than replace them with `FFFD`
+    let dbg = format!("{value:?}");
+    dbg.trim_matches('"').to_string()
+}
+
+/// A friendlier message than `invalid digit found in string` for integers.
+fn int_error(ty: &str, value: &str) -> Option<String> {
+    macro_rules! range {
+        ($($int:ident)*) => {
+            match ty {
+                $(stringify!($int) => ($int::MIN.to_string(), $int::MAX.to_string()),)*
+                _ => return None,
+            }
+        };
+    }
+    let (min, max) = range!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let res = if !digits.is_empty() && digits.bytes().all(|it| it.is_ascii_digit()) {
+        format!("expected `{ty}`, a number between {min} and {max}")
+    } else if min == "0" {
+        format!("expected `{ty}`, a non-negative whole number")
+    } else {
+        format!("expected `{ty}`, a whole number")
+    };
+    Some(res)
+}
 
-    pub fn optional<T>(&self, flag: &str, mut vals: Vec<T>) -> Result<Option<T>> {
-        if vals.len() > 1 {
-            bail!("Flag specified more than once: `{flag}`")
+/// Splits a delimited value. As in Windows command lines, backslashes are
+/// literal unless they precede a delimiter. There, each pair of them stands for
+/// one backslash, and an odd one escapes the delimiter.
+fn split_value(value: OsString, delimiter: char) -> Vec<OsString> {
+    if value.is_empty() {
+        return Vec::new();
+    }
+    if let Some(str) = value.to_str() {
+        let chars = str.chars().collect::<Vec<_>>();
+        return split_escaped(&chars, delimiter, '\\')
+            .into_iter()
+            .map(|it| OsString::from(it.into_iter().collect::<String>()))
+            .collect();
+    }
+    #[cfg(unix)]
+    if delimiter.is_ascii() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+        return split_escaped(value.as_bytes(), delimiter as u8, b'\\')
+            .into_iter()
+            .map(OsString::from_vec)
+            .collect();
+    }
+    // Can't split non-utf8 values portably, pass them through as a single element.
+    vec![value]
+}
+
+fn split_escaped<T: Copy + PartialEq>(items: &[T], delimiter: T, backslash: T) -> Vec<Vec<T>> {
+    let mut res = Vec::new();
+    let mut cur = Vec::new();
+    let mut backslashes = 0;
+    for &it in items {
+        if it == backslash {
+            backslashes += 1;
+            continue;
         }
-        Ok(vals.pop())
+        if it == delimiter {
+            cur.extend(iter::repeat_n(backslash, backslashes / 2));
+            if backslashes % 2 == 1 {
+                cur.push(delimiter);
+            } else {
+                res.push(mem::take(&mut cur));
+            }
+        } else {
+            cur.extend(iter::repeat_n(backslash, backslashes));
+            cur.push(it);
+        }
+        backslashes = 0;
     }
+    cur.extend(iter::repeat_n(backslash, backslashes));
+    res.push(cur);
+    res
+}
 
-    pub fn required<T>(&self, flag: &str, mut vals: Vec<T>) -> Result<T> {
-        if vals.len() > 1 {
-            bail!("Flag specified more than once: `{flag}`")
+/// Inverse of `split_escaped`.
+fn join_escaped<T: Copy + PartialEq>(values: &[Vec<T>], delimiter: T, backslash: T) -> Vec<T> {
+    let mut res = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            res.push(delimiter);
         }
-        vals.pop().ok_or_else(|| {
-            format_err!("Flag is required: `{flag}`. Use `help` for more information")
-        })
+        let mut backslashes = 0;
+        for &it in value {
+            if it == delimiter {
+                res.extend(iter::repeat_n(backslash, backslashes + 1));
+            }
+            backslashes = if it == backslash { backslashes + 1 } else { 0 };
+            res.push(it);
+        }
+        if i + 1 < values.len() {
+            res.extend(iter::repeat_n(backslash, backslashes));
+        }
+    }
+    res
+}
+
+/// Serializes parsed flags back into command line arguments.
+#[derive(Default)]
+pub struct Unparser {
+    after_double_dash: bool,
+    args: Vec<OsString>,
+}
+
+impl Unparser {
+    pub fn flag(&mut self, flag: &str) {
+        self.args.push(flag.into())
+    }
+
+    pub fn value(&mut self, value: impl AsRef<OsStr>) {
+        self.args.push(value.as_ref().to_os_string())
+    }
+
+    pub fn values<T: AsRef<OsStr>>(&mut self, values: impl Iterator<Item = T>, delimiter: char) {
+        let values = values.map(|it| it.as_ref().to_os_string()).collect::<Vec<_>>();
+        if let Some(strs) = values.iter().map(|it| it.to_str()).collect::<Option<Vec<_>>>() {
+            let chars = strs.iter().map(|it| it.chars().collect()).collect::<Vec<Vec<char>>>();
+            let joined = join_escaped(&chars, delimiter, '\\').into_iter().collect::<String>();
+            self.args.push(joined.into());
+            return;
+        }
+        #[cfg(unix)]
+        if delimiter.is_ascii() {
+            use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+            let bytes = values.iter().map(|it| it.as_bytes().to_vec()).collect::<Vec<_>>();
+            self.args.push(OsString::from_vec(join_escaped(&bytes, delimiter as u8, b'\\')));
+            return;
+        }
+        let mut buf = OsString::new();
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                buf.push(delimiter.encode_utf8(&mut [0; 4]));
+            }
+            buf.push(value);
+        }
+        self.args.push(buf)
+    }
+
+    pub fn subcommand(&mut self, name: &str) {
+        self.after_double_dash = false;
+        self.args.push(name.into())
+    }
+
+    /// Pushes a positional argument, preceded by `--` if it would otherwise
+    /// parse as a flag, or as `help` in a command that accepts it.
+    pub fn arg(&mut self, arg: impl AsRef<OsStr>, help_command: bool) {
+        let arg = arg.as_ref();
+        let s = arg.to_str().unwrap_or_default();
+        if !self.after_double_dash && (s.starts_with('-') || (help_command && s == "help")) {
+            self.after_double_dash = true;
+            self.args.push("--".into());
+        }
+        self.args.push(arg.to_os_string())
+    }
+
+    pub fn finish(self) -> Vec<OsString> {
+        self.args
     }
 }
@@ -0,0 +1,110 @@
+//! Splitting of a command line string into arguments, following POSIX shell
+//! quoting rules.
+
+use std::{ffi::OsString, iter::Peekable, str::Chars};
+
+use crate::{Error, Result};
+
+pub(crate) fn split(command_line: &str) -> Result<Vec<OsString>> {
+    let mut res = Vec::new();
+    let mut chars = command_line.chars().peekable();
+    // `None` if we are between words, `Some` even for an empty quoted word.
+    let mut word: Option<String> = None;
+    while let Some(c) = chars.next() {
+        match c {
+            // Only the characters in the default `IFS` separate words.
+            ' ' | '\t' | '\n' => {
+                if let Some(word) = word.take() {
+                    res.push(word.into());
+                }
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(Error::new("Unmatched `'` in command line")),
+                    }
+                }
+            }
+            '"' => double_quoted(&mut chars, word.get_or_insert_with(String::new))?,
+            '\\' => match chars.next() {
+                // Line continuation.
+                Some('\n') => (),
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => return Err(Error::new("Trailing backslash in command line")),
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(word) = word {
+        res.push(word.into());
+    }
+    Ok(res)
+}
+
+fn double_quoted(chars: &mut Peekable<Chars<'_>>, word: &mut String) -> Result<()> {
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(()),
+            // Inside double quotes, a backslash escapes only a few characters.
+            Some('\\') => match chars.peek() {
+                Some('\n') => {
+                    chars.next();
+                }
+                Some(&c @ ('$' | '`' | '"' | '\\')) => {
+                    chars.next();
+                    word.push(c);
+                }
+                _ => word.push('\\'),
+            },
+            Some(c) => word.push(c),
+            None => return Err(Error::new("Unmatched `\"` in command line")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn check(command_line: &str, expected: &[&str]) {
+        let args = super::split(command_line).unwrap();
+        assert_eq!(args, expected, "{command_line:?}");
+    }
+
+    fn check_err(command_line: &str, expected: &str) {
+        let err = super::split(command_line).unwrap_err();
+        assert_eq!(err.to_string(), expected, "{command_line:?}");
+    }
+
+    #[test]
+    fn words() {
+        check("", &[]);
+        check(" \t\n", &[]);
+        check("a  b\tc\nd", &["a", "b", "c", "d"]);
+        check("a\u{a0}b\rc", &["a\u{a0}b\rc"]);
+    }
+
+    #[test]
+    fn quotes() {
+        check(r#"'a b' "c d" e'f'"g""#, &["a b", "c d", "efg"]);
+        check(r#"'a\b' "a\b" 'a"b' "a'b""#, &[r"a\b", r"a\b", r#"a"b"#, "a'b"]);
+        check(r#"'' "" a'' """#, &["", "", "a", ""]);
+    }
+
+    #[test]
+    fn escapes() {
+        check(r"a\ b \'c\' \\", &["a b", "'c'", r"\"]);
+        check("a\\\nb", &["ab"]);
+        check(r#""\$ \` \" \\ \a""#, &[r#"$ ` " \ \a"#]);
+        check("\"a\\\nb\"", &["ab"]);
+    }
+
+    #[test]
+    fn errors() {
+        check_err("'a", "Unmatched `'` in command line");
+        check_err(r#"a "b"#, "Unmatched `\"` in command line");
+        check_err(r#""a\""#, "Unmatched `\"` in command line");
+        check_err(r"a\", "Trailing backslash in command line");
+    }
+}